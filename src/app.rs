@@ -1,26 +1,38 @@
 use crate::{
-    conjugations::VerbConjugations,
-    app_event::{
-        AppEvent,
-        AppEvents
-    },
-    lookup_event::LookupEvent, user_error::UserError, definitions::WordDefinitions, wordreference::wordreference_utils
+    classification::{self, Classification},
+    comparison::Comparison,
+    conjugations::{ConjugationTable, VerbConjugations},
+    conjugator::RegularConjugator,
+    app_event::{self, AppEvent, AppEventHandler},
+    lookup_event::{LookupEvent, LookupEventHandler, LookupRequest}, user_error::UserError, definitions::WordDefinitions, wordreference::wordreference_utils,
+    config::{self, Config, KeyBinding, Theme},
+    variants::{self, Variant},
+    quiz::{Grade, Quiz},
+    deck::{self, Card, Review},
+    status::{LoadStage, Status},
+    table_layout,
 };
 
 use std::{io, sync::Arc, time::Duration, cmp::max};
-use tokio::time::Instant;
+use unicode_width::UnicodeWidthChar;
+use tokio::{
+    sync::{mpsc::{Receiver, Sender}, watch},
+    task::JoinHandle,
+};
 use tui::{
     backend::{Backend},
-    layout::{Constraint, Layout, Direction},
-    style::{Color, Modifier, Style},
-    widgets::{Block, Borders, Cell, Row, Table, TableState, Paragraph, Wrap, LineGauge},
-    Frame, Terminal, symbols,
+    layout::{Constraint, Layout, Direction, Rect},
+    style::{Modifier, Style},
+    widgets::{Block, Borders, Cell, Row, Table, TableState, Paragraph, Wrap},
+    text::{Span, Spans},
+    Frame, Terminal,
 };
 
 pub struct TableData {
     title: String,
     header: Vec<String>,
     items: Vec<Vec<String>>,
+    highlighted: Vec<(usize, usize)>, // (row, column) cells to emphasise
 }
 
 impl TableData {
@@ -29,84 +41,165 @@ impl TableData {
             title: String::new(),
             header: Vec::new(),
             items: Vec::new(),
+            highlighted: Vec::new(),
         }
     }
 }
 
+/// Where the last frame drew what a click can land on.
+#[derive(Default)]
+struct Regions {
+    prompt: Rect,
+    rows: Vec<(Rect, usize)>, // table rows on screen and their index
+    tabs: Vec<(Rect, usize)>, // the numbered tabs in a table's title
+}
+
+fn contains(area: Rect, column: u16, row: u16) -> bool {
+    (area.left()..area.right()).contains(&column) && (area.top()..area.bottom()).contains(&row)
+}
+
 pub struct App {
-    state: TableState,
+    selected_row: Option<usize>,
+    table_scroll: usize, // the first row shown
+    regions: Regions,
     conjugations: VerbConjugations,
+    classification: Option<Classification>,
     definitions: WordDefinitions,
+    comparison: Option<Comparison>,
+    quiz: Option<Quiz>,
+    review: Option<Review>,
     table_data: TableData,
     input: String,
+    cursor: usize, // in characters, the end once the input is shorter
     current_table: usize,
     pub language: String,
+    pub native_language: String, // what def translates from and trans to
     error: String,
-    io_tx: tokio::sync::mpsc::Sender<AppEvent>,
-    lookup_tx: tokio::sync::mpsc::Sender<LookupEvent>,
+    lookup_handler: Arc<LookupEventHandler>,
     closed: bool,
-    loading: bool,
-    load_start: Instant,
+    num_loading: usize, // lookups in progress
+    lookups: Vec<JoinHandle<()>>,
+    lookup_generation: u64, // bumped on cancelling, so stale updates are dropped
+    writes: Vec<JoinHandle<()>>, // grades and deck changes, never cancelled
+    status: Option<Status>, // from the first lookup on
+    show_all_tenses: bool,
+    grid_scroll: usize,
+    variant: Variant,
+    theme: Theme,
+    cancel_key: KeyBinding, // shown in the status bar while loading
+    tick_rate: Duration,
 }
 
 impl App {
     pub fn new(
-        io_tx: tokio::sync::mpsc::Sender<AppEvent>,
-        lookup_tx: tokio::sync::mpsc::Sender<LookupEvent>,
+        lookup_handler: Arc<LookupEventHandler>,
+        config: &Config,
     ) -> App {
-        let default_language = "french".to_string();
         App {
-            state: TableState::default(),
+            selected_row: None,
+            table_scroll: 0,
+            regions: Regions::default(),
             conjugations: VerbConjugations::empty(),
+            classification: None,
             definitions: WordDefinitions::empty(),
+            comparison: None,
+            quiz: None,
+            review: None,
             table_data: TableData::new(),
             input: String::new(),
+            cursor: 0,
             current_table: 0,
-            language: default_language,
-            io_tx,
-            lookup_tx,
+            language: config.defaults.language.clone(),
+            native_language: config.defaults.native_language.clone(),
+            lookup_handler,
             error: "".to_string(),
             closed: false,
-            loading: false,
-            load_start: Instant::now(),
+            num_loading: 0,
+            lookups: Vec::new(),
+            lookup_generation: 0,
+            writes: Vec::new(),
+            status: None,
+            show_all_tenses: false,
+            grid_scroll: 0,
+            variant: Variant::default(),
+            theme: config.theme.theme(),
+            cancel_key: config.keys.quit,
+            tick_rate: Duration::from_millis(config.ui.tick_rate_ms),
         }
     }
 
     pub fn start_load(&mut self) {
-        self.loading = true;
-        self.load_start = Instant::now();
+        if self.num_loading == 0 {
+            self.status.get_or_insert_with(Status::new).start();
+        }
+        self.num_loading += 1;
     }
 
     pub fn end_load(&mut self) {
-        self.loading = false;
+        self.num_loading = self.num_loading.saturating_sub(1);
+        if self.num_loading == 0 {
+            if let Some(status) = self.status.as_mut() {
+                status.finish();
+            }
+        }
     }
 
-    fn progress_smoothing(&self, current_load_time: u128, max_load_time: u128) -> f64 {
-        let current_load_time = current_load_time as f64;
-        let max_load_time = max_load_time as f64;
+    /// Shows what a lookup is doing, unless it was cancelled meanwhile.
+    pub fn report_stage(&mut self, stage: LoadStage) {
+        if let (Some(status), true) = (self.status.as_mut(), self.num_loading > 0) {
+            status.report(stage);
+        }
+    }
 
-        // uses the sigmoid function to smooth the progress bar
-        let unsmoothed_progress = 4.0 * ((current_load_time / max_load_time) - 0.5);
-        let denominator = 1.0 + (-unsmoothed_progress).exp();
-        let smoothed_progress = 1.0 / denominator;
+    pub fn is_loading(&self) -> bool {
+        self.num_loading > 0
+    }
 
-        smoothed_progress
+    pub fn track_lookup(&mut self, lookup: JoinHandle<()>) {
+        self.lookups.retain(|lookup| !lookup.is_finished());
+        self.lookups.push(lookup);
     }
 
-    // Gets progress as a ratio
-    pub fn get_progress(&mut self) -> f64 {
-        if !self.loading {
-            return 1.0;
-        }
+    pub fn track_write(&mut self, write: JoinHandle<()>) {
+        self.writes.retain(|write| !write.is_finished());
+        self.writes.push(write);
+    }
 
-        let loaded_for_duration = self.load_start.elapsed();
-        let loaded_for = loaded_for_duration
-            .as_millis();
+    /// Hands over the lookups and writes still running, for shutdown to wait on.
+    pub fn take_lookups(&mut self) -> Vec<JoinHandle<()>> {
+        self.lookups
+            .drain(..)
+            .chain(self.writes.drain(..))
+            .filter(|lookup| !lookup.is_finished())
+            .collect()
+    }
 
-        let max_load_time = 3000;
-        let progress = self.progress_smoothing(loaded_for, max_load_time);
+    /// The lookups dispatched since the last cancel share a generation.
+    pub fn lookup_generation(&self) -> u64 {
+        self.lookup_generation
+    }
 
-        progress
+    /// Stops every lookup in progress, leaving the displayed results as they were.
+    pub fn cancel_lookups(&mut self) {
+        for lookup in self.lookups.drain(..) {
+            lookup.abort();
+        }
+        self.lookup_generation += 1;
+        self.num_loading = 0;
+        if let Some(status) = self.status.as_mut() {
+            status.finish();
+        }
+        self.set_error(UserError {
+            message: "Lookup cancelled".to_string(),
+        });
+    }
+
+    /// What the status bar says, only the languages until a first lookup.
+    fn status_line(&self) -> String {
+        match &self.status {
+            Some(status) => status.line(&self.language, &self.native_language, self.cancel_key),
+            None => format!("{} ↔ {}", self.language, self.native_language),
+        }
     }
 
     pub fn close(&mut self) {
@@ -117,21 +210,55 @@ impl App {
         self.input.clone()
     }
 
+    /// Byte offset of the cursor in the input.
+    fn cursor_offset(&self) -> usize {
+        self.input
+            .char_indices()
+            .nth(self.cursor)
+            .map_or(self.input.len(), |(offset, _)| offset)
+    }
+
     pub fn pop_char(&mut self) {
-        self.input.pop();
+        self.cursor = self.cursor.min(self.input.chars().count());
+        if self.cursor > 0 {
+            self.cursor -= 1;
+            let offset = self.cursor_offset();
+            self.input.remove(offset);
+        }
     }
 
     pub fn put_char(&mut self, c: char) {
-        self.input.push(c);
+        let offset = self.cursor_offset();
+        self.input.insert(offset, c);
+        self.cursor = self.input[..offset].chars().count() + 1;
     }
 
     pub fn set_conjugations(&mut self, conjugations: VerbConjugations) {
         self.clear_tables();
+        self.classification = classification::classify(&conjugations, &conjugations.language);
         self.conjugations = conjugations;
         self.current_table = 0;
         self.load_conjugation_tables();
     }
 
+    /// Opens the conjugation table matching a tense query such as "subj imparfait".
+    pub fn open_tense(&mut self, tense_query: &str) -> Result<(), UserError> {
+        let language = self.conjugations.language.clone();
+        match self.conjugations.find_table(tense_query, &language) {
+            Some(table_index) => {
+                self.current_table = table_index;
+                self.load_conjugation_tables();
+                Ok(())
+            }
+            None => Err(UserError {
+                message: format!(
+                    "No tense of '{}' matches '{tense_query}'",
+                    self.conjugations.verb,
+                )
+            }),
+        }
+    }
+
     pub fn set_definitions(&mut self, definitions: WordDefinitions) {
         self.clear_tables();
         self.definitions = definitions;
@@ -139,6 +266,26 @@ impl App {
         self.load_definition_tables();
     }
 
+    /// Lists cached verbs that conjugate like a model verb.
+    pub fn set_model_verbs(&mut self, model: &str, verbs: Vec<(String, Classification)>) {
+        self.clear_tables();
+
+        let items = verbs
+            .into_iter()
+            .map(|(verb, classification)| vec![verb, classification.group])
+            .collect();
+
+        self.table_data = TableData {
+            title: format!("Verbs conjugating like {model} {}", self.language),
+            header: vec![
+                "Verb".to_string(),
+                "Group".to_string(),
+            ],
+            items,
+            highlighted: Vec::new(),
+        };
+    }
+
     pub fn set_error(&mut self, error: UserError) {
         self.error = error.message;
     }
@@ -147,34 +294,387 @@ impl App {
         self.error = "".to_string();
     }
 
+    pub fn set_comparison(&mut self, comparison: Comparison) {
+        self.clear_tables();
+        self.comparison = Some(comparison);
+        self.current_table = 0;
+        self.load_comparison_table();
+    }
+
+    pub fn start_quiz(&mut self, quiz: Quiz) {
+        self.clear_tables();
+        self.quiz = Some(quiz);
+        self.load_quiz_table();
+    }
+
+    /// While a quiz runs, entries are answers rather than commands.
+    fn handle_quiz_entry(&mut self) {
+        let typed = self.input.trim().to_string();
+        self.clear_input();
+
+        let Some(quiz) = self.quiz.as_mut() else {
+            return;
+        };
+
+        if typed == "stop" {
+            let title = format!("Quiz finished: {}", quiz.score());
+            self.clear_tables();
+            self.table_data = TableData {
+                title,
+                header: Vec::new(),
+                items: Vec::new(),
+                highlighted: Vec::new(),
+            };
+            return;
+        }
+
+        quiz.answer(&typed);
+        self.load_quiz_table();
+    }
+
+    fn load_quiz_table(&mut self) {
+        let Some(quiz) = &self.quiz else {
+            return;
+        };
+
+        let question = &quiz.question;
+        let mut items = vec![
+            vec!["verb".to_string(), question.verb.clone()],
+            vec!["tense".to_string(), question.tense.clone()],
+            vec!["person".to_string(), question.pronoun.clone()],
+        ];
+        let mut highlighted = Vec::new();
+
+        if let Some(result) = &quiz.last_result {
+            let verdict = match (result.grade, result.counted_correct) {
+                (Grade::Correct, _) => "correct".to_string(),
+                (Grade::WrongAccents, true) => format!("correct, mind the accents: {}", result.question.answer),
+                (Grade::WrongAccents, false) => "wrong, check the accents".to_string(),
+                (Grade::Wrong, _) => "wrong".to_string(),
+            };
+            let typed = match result.typed.is_empty() {
+                true => "(skipped)".to_string(),
+                false => result.typed.clone(),
+            };
+
+            items.push(vec![String::new(), String::new()]);
+            items.push(vec!["last answer".to_string(), format!("{typed}: {verdict}")]);
+            highlighted.push((items.len() - 1, 1));
+
+            // on a miss, the row of the table it came from
+            if !result.counted_correct {
+                let question = &result.question;
+                items.push(vec![
+                    "answer".to_string(),
+                    format!("{} {} ({}, {})", question.pronoun, question.answer, question.verb, question.tense),
+                ]);
+            }
+        }
+
+        self.table_data = TableData {
+            title: format!(
+                "Quiz {}: {} (type stop to finish{})",
+                quiz.options.language,
+                quiz.score(),
+                match quiz.options.lenient {
+                    true => ", accents optional",
+                    false => "",
+                },
+            ),
+            header: vec![
+                "Question".to_string(),
+                "Conjugate".to_string(),
+            ],
+            items,
+            highlighted,
+        };
+    }
+
+    /// Lists the deck's cards, soonest due first.
+    pub fn set_deck(&mut self, cards: Vec<Card>, note: &str) {
+        self.clear_tables();
+
+        let now = deck::now();
+        let num_due = cards.iter().filter(|card| card.due <= now).count();
+        let items = cards
+            .iter()
+            .map(|card| {
+                let due = match card.due - now {
+                    seconds if seconds <= 0 => "due".to_string(),
+                    seconds => format!("in {} days", (seconds + 86399) / 86400),
+                };
+                vec![card.front.clone(), card.back.clone(), due]
+            })
+            .collect();
+
+        self.table_data = TableData {
+            title: format!(
+                "Deck {}: {} cards, {num_due} due{}",
+                self.language,
+                cards.len(),
+                match note.is_empty() {
+                    true => String::new(),
+                    false => format!(" ({note})"),
+                },
+            ),
+            header: vec![
+                "Front".to_string(),
+                "Back".to_string(),
+                "Due".to_string(),
+            ],
+            items,
+            highlighted: Vec::new(),
+        };
+    }
+
+    pub fn set_export_summary(&mut self, path: &str, num_translations: usize, num_conjugations: usize) {
+        self.clear_tables();
+        self.table_data = TableData {
+            title: format!("Exported {} notes to {path}", num_translations + num_conjugations),
+            header: vec![
+                "Note type".to_string(),
+                "Notes".to_string(),
+            ],
+            items: vec![
+                vec!["translation".to_string(), num_translations.to_string()],
+                vec!["conjugation".to_string(), num_conjugations.to_string()],
+            ],
+            highlighted: Vec::new(),
+        };
+    }
+
+    pub fn start_review(&mut self, review: Review) {
+        self.clear_tables();
+        self.review = Some(review);
+        self.load_review_table();
+    }
+
+    /// While reviewing, an empty entry reveals the answer and 1 to 4 grades it.
+    fn handle_review_entry(&mut self) {
+        let typed = self.input.trim().to_string();
+        self.clear_input();
+
+        let Some(review) = self.review.as_mut() else {
+            return;
+        };
+
+        match typed.as_str() {
+            "stop" => {
+                self.finish_review();
+                return;
+            }
+            "" => review.revealed = true,
+            "1" | "2" | "3" | "4" if !review.revealed => {
+                self.set_error(UserError {
+                    message: "Press Enter to reveal the answer before grading it".to_string(),
+                });
+            }
+            "1" | "2" | "3" | "4" => {
+                let grade = typed.parse::<u8>().expect("grades are digits");
+                if let Some(card) = review.grade(grade) {
+                    self.dispatch_lookup(LookupEvent::Grade { card, grade });
+                }
+            }
+            _ => {
+                self.set_error(UserError {
+                    message: "Press Enter to reveal the answer, grade it from 1 to 4 \
+                    or type stop to finish".to_string(),
+                });
+            }
+        }
+
+        self.load_review_table();
+    }
+
+    fn finish_review(&mut self) {
+        let reviewed = self.review.as_ref().map(|review| review.reviewed).unwrap_or(0);
+        self.clear_tables();
+        self.table_data = TableData {
+            title: format!("Review finished: {reviewed} cards reviewed"),
+            header: Vec::new(),
+            items: Vec::new(),
+            highlighted: Vec::new(),
+        };
+    }
+
+    fn load_review_table(&mut self) {
+        let Some(review) = &self.review else {
+            return;
+        };
+        let Some(card) = review.card() else {
+            self.finish_review();
+            return;
+        };
+
+        let kind = match card.kind.as_str() {
+            "conj" => "conjugate",
+            "def" => "translate",
+            _ => "define",
+        };
+        let mut items = vec![
+            vec![kind.to_string(), card.front.clone()],
+        ];
+        let mut highlighted = Vec::new();
+
+        match review.revealed {
+            true => {
+                items.push(vec!["answer".to_string(), card.back.clone()]);
+                highlighted.push((1, 1));
+                items.push(vec![String::new(), String::new()]);
+                items.push(vec!["grade".to_string(), "1 again, 2 hard, 3 good, 4 easy".to_string()]);
+            }
+            false => items.push(vec!["answer".to_string(), "(press Enter to reveal)".to_string()]),
+        }
+
+        self.table_data = TableData {
+            title: format!(
+                "Review {}: card {} of {} (type stop to finish)",
+                card.language,
+                review.current + 1,
+                review.cards.len(),
+            ),
+            header: vec![
+                "Card".to_string(),
+                String::new(),
+            ],
+            items,
+            highlighted,
+        };
+    }
+
     pub fn clear_tables(&mut self) {
         self.conjugations = VerbConjugations::empty();
+        self.classification = None;
+        self.definitions = WordDefinitions::empty();
+        self.comparison = None;
+        self.quiz = None;
+        self.review = None;
         self.table_data = TableData::new();
         self.current_table = 0;
-        self.state = TableState::default();
+        self.selected_row = None;
+        self.table_scroll = 0;
+        self.grid_scroll = 0;
     }
 
-    pub fn command_body(&self) -> String {
-        self.input
-            .split(" ")
-            .skip(1)
-            .collect::<Vec<&str>>()
-            .join(" ")
+    pub fn set_all_tenses(&mut self, show_all_tenses: bool) {
+        self.show_all_tenses = show_all_tenses;
+        self.grid_scroll = 0;
     }
 
-    pub fn clear_input(&mut self) {
-        self.input = "".to_string();
+    pub fn toggle_all_tenses(&mut self) {
+        self.set_all_tenses(!self.show_all_tenses);
+    }
+
+    pub fn set_variant(&mut self, variant: Variant) {
+        self.variant = variant;
+        self.load_conjugation_tables();
+    }
+
+    pub fn toggle_negative(&mut self) {
+        self.set_variant(Variant {
+            negative: !self.variant.negative,
+            ..self.variant
+        });
     }
 
-    pub async fn dispatch_io(&mut self, action: AppEvent) {
-        if let Err(_err) = self.io_tx.send(action).await {
+    pub fn toggle_interrogative(&mut self) {
+        self.set_variant(Variant {
+            interrogative: !self.variant.interrogative,
+            ..self.variant
+        });
+    }
+
+    /// Pronoun/conjugation rows of a table in the selected sentence form.
+    fn table_rows(&self, table: &ConjugationTable) -> Vec<Vec<String>> {
+        variants::transform_table(&self.conjugations, table, &self.conjugations.language, self.variant)
+    }
+
+    /// Names the sentence form in titles unless it is the plain affirmative.
+    fn variant_note(&self) -> String {
+        match self.variant.is_affirmative() {
+            true => String::new(),
+            false => format!(", {}", self.variant.label()),
+        }
+    }
+
+    fn grid_open(&self) -> bool {
+        self.show_all_tenses && !self.conjugations.conjugation_tables.is_empty()
+    }
+
+    pub fn scroll_up(&mut self) {
+        match self.grid_open() {
+            true => self.grid_scroll = self.grid_scroll.saturating_sub(1),
+            false => self.table_scroll = self.table_scroll.saturating_sub(1),
+        }
+    }
+
+    pub fn scroll_down(&mut self) {
+        if self.grid_open() {
+            let num_moods = self.conjugations.tables_by_mood().len();
+            if self.grid_scroll + 1 < num_moods {
+                self.grid_scroll += 1;
+            }
+        } else if self.table_scroll + 1 < self.table_data.items.len() {
+            self.table_scroll += 1;
+        }
+    }
+
+    /// Selects the table row, switches to the table of the tab or moves the
+    /// prompt's cursor under a click.
+    pub fn click(&mut self, column: u16, row: u16) {
+        let clicked = |regions: &[(Rect, usize)]| {
+            regions
+                .iter()
+                .find(|(area, _)| contains(*area, column, row))
+                .map(|(_, index)| *index)
         };
+
+        if let Some(index) = clicked(&self.regions.tabs) {
+            self.open_table(index);
+        } else if let Some(index) = clicked(&self.regions.rows) {
+            self.selected_row = Some(index);
+        } else if contains(self.regions.prompt, column, row) {
+            // the character under the click, or the end when past the text
+            let text_x = self.regions.prompt.x + 1;
+            let mut width = 0;
+            self.cursor = self.input
+                .chars()
+                .take_while(|c| {
+                    width += c.width().unwrap_or(0) as u16;
+                    text_x + width <= column
+                })
+                .count();
+        }
     }
 
-    pub async fn dispatch_lookup(&mut self, action: LookupEvent) {
-        if let Err(_err) = self.lookup_tx.send(action).await {
+    pub fn clear_input(&mut self) {
+        self.input = "".to_string();
+        self.cursor = 0;
+    }
+
+    /// Runs a lookup as a task of its own, which Esc or Ctrl+C can cancel
+    /// unless it is a write, handing it the command typed so far.
+    pub fn dispatch_lookup(&mut self, action: LookupEvent) {
+        let request = LookupRequest {
+            input: std::mem::take(&mut self.input),
+            language: self.language.clone(),
+            native_language: self.native_language.clone(),
+            generation: self.lookup_generation,
         };
 
+        // writes don't show as loading
+        let is_write = action.is_write();
+        if !is_write {
+            self.start_load();
+        }
+        let handler = Arc::clone(&self.lookup_handler);
+        let lookup = tokio::spawn(async move {
+            handler.handle_lookup_event(action, request).await;
+        });
+        match is_write {
+            true => self.track_write(lookup),
+            false => self.track_lookup(lookup),
+        }
     }
 
     fn remove_prefix(&mut self) {
@@ -191,22 +691,46 @@ impl App {
             .collect::<String>()
     }
 
-    pub async fn set_verb(&mut self) {
-        self.dispatch_lookup(LookupEvent::Verb).await;
+    pub fn set_verb(&mut self) {
+        self.dispatch_lookup(LookupEvent::Verb);
+    }
+
+    pub fn set_comparison_verbs(&mut self) {
+        self.dispatch_lookup(LookupEvent::Compare);
+    }
+
+    pub fn set_quiz(&mut self) {
+        self.dispatch_lookup(LookupEvent::Quiz);
     }
 
-    pub async fn set_word_definition(&mut self) {
-        self.dispatch_lookup(LookupEvent::Definition).await;
+    pub fn set_model_lookup(&mut self) {
+        self.dispatch_lookup(LookupEvent::Like);
     }
 
-    pub async fn set_word_translation(&mut self) {
-        self.dispatch_lookup(LookupEvent::Translation).await;
+    pub fn set_deck_command(&mut self) {
+        self.dispatch_lookup(LookupEvent::Deck);
+    }
+
+    pub fn set_export(&mut self) {
+        self.dispatch_lookup(LookupEvent::Export);
+    }
+
+    pub fn set_review(&mut self) {
+        self.dispatch_lookup(LookupEvent::Review);
+    }
+
+    pub fn set_word_definition(&mut self) {
+        self.dispatch_lookup(LookupEvent::Definition);
+    }
+
+    pub fn set_word_translation(&mut self) {
+        self.dispatch_lookup(LookupEvent::Translation);
     }
 
     fn set_language(&mut self) {
         self.remove_prefix();
         let language: String = self.input.drain(..).collect();
-        if wordreference_utils::map_language(language.clone()).len() > 0 {
+        if !wordreference_utils::map_language(language.clone()).is_empty() {
             self.language = language;
         } else {
             self.set_error(UserError {
@@ -215,6 +739,41 @@ impl App {
         }
     }
 
+    /// Switches to a named theme, or lists them when none is given.
+    fn set_theme(&mut self) {
+        let name = self.input
+            .split_whitespace()
+            .nth(1)
+            .map(|name| name.to_lowercase());
+        self.clear_input();
+
+        match name.as_deref().and_then(Theme::named) {
+            Some(theme) => self.theme = theme,
+            None => self.display_themes(name),
+        }
+    }
+
+    /// Lists the themes, after a name that isn't one of them or when none was given.
+    fn display_themes(&mut self, unknown_name: Option<String>) {
+        self.clear_tables();
+
+        let title = match unknown_name {
+            Some(name) => format!("Themes, '{name}' is not one of them"),
+            None => "Themes".to_string(),
+        };
+        let items = config::THEME_NAMES
+            .iter()
+            .map(|name| vec![name.to_string(), format!("theme {name}")])
+            .collect();
+
+        self.table_data = TableData {
+            title,
+            header: vec!["theme".to_string(), "command".to_string()],
+            items,
+            highlighted: Vec::new(),
+        };
+    }
+
     fn display_help(&mut self) {
         self.clear_tables();
 
@@ -229,23 +788,56 @@ impl App {
                 "lang <language>".to_string(),
                 "change the currently set language".to_string(),
             ],
+            vec![
+                "theme [name]".to_string(),
+                format!("list the themes or switch to one of {}", config::THEME_NAMES.join(", ")),
+            ],
+            vec![
+                "compare <verb> <verb>".to_string(),
+                "show two verbs side by side, prefix a verb with a language to compare across languages (e.g. es:venir)".to_string(),
+            ],
+            vec![
+                "like <verb>".to_string(),
+                "list cached verbs in the current language that conjugate like a model verb (e.g. venir)".to_string(),
+            ],
+            vec![
+                "quiz [-l] [lang=<language>] [tenses=<tense>,...] [verb ...]".to_string(),
+                "drill conjugations of cached verbs, optionally only some tenses (e.g. tenses=pres,subj_imp) or verbs; \
+                -l accepts answers missing accents, type stop to finish".to_string(),
+            ],
+            vec![
+                "deck [add | auto on | auto off]".to_string(),
+                "list the flashcards of the current language; def, trans and conj lookups are added automatically, \
+                or with deck add once auto is off".to_string(),
+            ],
+            vec![
+                "review".to_string(),
+                "review the due flashcards: Enter reveals the answer, then grade it 1 (again) to 4 (easy)".to_string(),
+            ],
+            vec![
+                "export anki|csv|tsv <file>".to_string(),
+                "export cached translations and conjugations of the current language as an Anki deck (.apkg) \
+                or a CSV/TSV file for Anki or Quizlet".to_string(),
+            ],
             vec![
                 "def <word>".to_string(),
-                "translates a word from english to the current language".to_string(),
+                format!("translates a word from {} to the current language", self.native_language),
             ],
             vec![
                 "trans <word>".to_string(),
                 "gives the definition of the word in the current language".to_string(),
             ],
             vec![
-                "conj <verb>".to_string(),
-                "conjugate a verb in the current language".to_string(),
+                "conj [-a] [-n] [-i] <verb> [tense]".to_string(),
+                "conjugate a verb in the current language, optionally opening a tense (e.g. subj imparfait); \
+                -a or Tab shows all tenses, -n or F2 the negative and -i or F3 the interrogative".to_string(),
             ],
         ];
         let help_table = TableData {
             title,
             header,
             items,
+            highlighted: Vec::new(),
         };
 
         self.table_data = help_table;
@@ -265,35 +857,102 @@ impl App {
         self.clear_input();
     }
 
-    pub async fn handle_entry(&mut self) {
+    pub fn handle_entry(&mut self) {
         self.clear_error();
+        if self.quiz.is_some() {
+            self.handle_quiz_entry();
+            return;
+        }
+        if self.review.is_some() {
+            self.handle_review_entry();
+            return;
+        }
+
         let string = self.input.as_str();
         match string {
             _ if string.starts_with("lang")     => self.set_language(),
-            _ if string.starts_with("conj")     => self.set_verb().await,
-            _ if string.starts_with("def")      => self.set_word_definition().await,
-            _ if string.starts_with("trans")    => self.set_word_translation().await,
+            _ if string.starts_with("conj")     => self.set_verb(),
+            _ if string.starts_with("compare")  => self.set_comparison_verbs(),
+            _ if string.starts_with("like")     => self.set_model_lookup(),
+            _ if string.starts_with("quiz")     => self.set_quiz(),
+            _ if string.starts_with("deck")     => self.set_deck_command(),
+            _ if string.starts_with("review")   => self.set_review(),
+            _ if string.starts_with("export")   => self.set_export(),
+            _ if string.starts_with("def")      => self.set_word_definition(),
+            _ if string.starts_with("trans")    => self.set_word_translation(),
+            _ if string.starts_with("theme")    => self.set_theme(),
             _ if string.starts_with("help")     => self.display_help(),
             _                                   => self.handle_error(),
         };
     }
 
+    /// Marks conjugations the generator produced offline.
+    fn generated_note(&self) -> &'static str {
+        match self.conjugations.generated {
+            true => " (generated, not verified)",
+            false => "",
+        }
+    }
+
+    /// The auxiliary and participles compound tenses are built from, e.g.
+    /// "auxiliary: être   past participle: venu   present participle: venant".
+    fn verb_info(&self) -> Option<String> {
+        if self.conjugations.conjugation_tables.is_empty() {
+            return None;
+        }
+
+        let parts = [
+            ("auxiliary", &self.conjugations.auxiliary),
+            ("past participle", &self.conjugations.past_participle),
+            ("present participle", &self.conjugations.present_participle),
+        ]
+            .iter()
+            .filter_map(|(label, value)| value.as_ref().map(|value| format!("{label}: {value}")))
+            .collect::<Vec<String>>();
+
+        match parts.is_empty() {
+            true => None,
+            false => Some(parts.join("   ")),
+        }
+    }
+
+    /// The verb with its group and model, e.g. "devenir (3e groupe, conjugates like venir)".
+    fn verb_heading(&self) -> String {
+        match &self.classification {
+            Some(classification) => format!("{} ({})", self.conjugations.verb, classification.describe()),
+            None => self.conjugations.verb.clone(),
+        }
+    }
+
     pub fn load_conjugation_tables(&mut self) {
         if self.conjugations.conjugation_tables.len() > self.current_table {
-            let language = self.language.clone();
-            let items = self.conjugations
-                .conjugation_tables[self.current_table]
-                .conjugations_as_strings();
-            let tense = (&self.conjugations.conjugation_tables[self.current_table].tense)
-                .clone();
+            let language = self.conjugations.language.clone();
+            let table = &self.conjugations.conjugation_tables[self.current_table];
+            let items = self.table_rows(table);
+            let tense = table.label();
             let verb = self.conjugations.verb.clone();
+            let heading = self.verb_heading();
+
+            // forms that break the pattern of the verb's regular class
+            let highlighted = RegularConjugator::new(&language, &verb)
+                .map(|conjugator| conjugator.irregular_rows(table))
+                .unwrap_or_default()
+                .into_iter()
+                .map(|row| (row, 1))
+                .collect();
+
             self.table_data = TableData {
-                title: format!("{verb}: {tense} {language}"),
+                title: format!(
+                    "{heading}: {tense} {language}{}{}",
+                    self.variant_note(),
+                    self.generated_note(),
+                ),
                 header: vec![
                     "Pronouns".to_string(),
                     "Conjugations".to_string(),
                 ],
                 items,
+                highlighted,
             };
         }
     }
@@ -305,64 +964,107 @@ impl App {
                 title: self.definitions.title.clone(),
                 header: definitions.header.clone(),
                 items: definitions.definitions.clone(),
+                highlighted: Vec::new(),
+            };
+        }
+    }
+
+    pub fn load_comparison_table(&mut self) {
+        let comparison_table = self.comparison
+            .as_ref()
+            .and_then(|comparison| comparison.table(self.current_table));
+
+        if let Some(comparison_table) = comparison_table {
+            let highlighted = comparison_table.differing
+                .iter()
+                .flat_map(|row| [(*row, 1), (*row, 2)])
+                .collect();
+
+            self.table_data = TableData {
+                title: comparison_table.title,
+                header: comparison_table.header,
+                items: comparison_table.items,
+                highlighted,
             };
         }
     }
 
     fn table_open(&self) -> bool {
-        self.table_data.title.len() > 0
+        !self.table_data.title.is_empty()
+    }
+
+    fn num_tables(&self) -> usize {
+        match &self.comparison {
+            Some(comparison) => comparison.pairs.len(),
+            None => max(
+                self.conjugations.conjugation_tables.len(),
+                self.definitions.definitions.len(),
+            ),
+        }
+    }
+
+    fn load_current_table(&mut self) {
+        if self.comparison.is_some() {
+            self.load_comparison_table();
+        } else if !self.conjugations.conjugation_tables.is_empty() {
+            self.load_conjugation_tables();
+        } else if !self.definitions.definitions.is_empty() {
+            self.load_definition_tables();
+        }
+    }
+
+    fn open_table(&mut self, index: usize) {
+        self.current_table = index;
+        self.selected_row = None;
+        self.table_scroll = 0;
+        self.load_current_table();
     }
 
     pub fn next(&mut self) {
-        let conj_len = self.conjugations.conjugation_tables.len();
-        let def_len = self.definitions.definitions.len();
-        let num_tables = max(conj_len, def_len);
+        let num_tables = self.num_tables();
 
         if num_tables > 0 {
-            self.current_table = (self.current_table + 1) % num_tables;
-
-            if conj_len > 0 {
-                self.load_conjugation_tables();
-            } else if def_len > 0 {
-                self.load_definition_tables();
-            }
+            self.open_table((self.current_table + 1) % num_tables);
         }
     }
 
     pub fn prev(&mut self) {
-        let conj_len = self.conjugations.conjugation_tables.len();
-        let def_len = self.definitions.definitions.len();
-        let num_tables = max(conj_len, def_len);
+        let num_tables = self.num_tables();
 
         if num_tables > 0 {
-            self.current_table = (self.current_table + num_tables - 1) % num_tables;
-            self.load_conjugation_tables();
-
-            if conj_len > 0 {
-                self.load_conjugation_tables();
-            } else if def_len > 0 {
-                self.load_definition_tables();
-            }
+            self.open_table((self.current_table + num_tables - 1) % num_tables);
         }
     }
 }
 
-pub async fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &Arc<tokio::sync::Mutex<App>>) -> io::Result<()> {
-    let tick_rate = Duration::from_millis(200);
-    let mut app_events = AppEvents::new(tick_rate);
+/// Owns the app, applying key presses, clicks and lookup updates as they arrive
+/// and redrawing after each. It only ticks while the loading gauge animates.
+/// Returns once the app is closed or asked to shut down.
+pub async fn run_app<B: Backend>(
+    terminal: &mut Terminal<B>,
+    app: &mut App,
+    mut app_rx: Receiver<AppEvent>,
+    app_tx: Sender<AppEvent>,
+    handler: AppEventHandler,
+    mut shutdown: watch::Receiver<bool>,
+) -> io::Result<()> {
+    app_event::read_terminal(app_tx, app.tick_rate);
 
     loop {
-        let mut app = app.lock().await;
+        terminal.draw(|f| ui(f, app))?;
 
-        terminal.draw(|f| ui(f, &mut app))?;
-
-        match app_events.next().await.unwrap() {
-            AppEvent::Input(key_event) => {
-                app.dispatch_io(AppEvent::Input(key_event)).await;
-            },
-            AppEvent::Tick => {
-                app.dispatch_io(AppEvent::Tick).await;
-            },
+        let app_event = tokio::select! {
+            app_event = app_rx.recv() => app_event,
+            _ = tokio::time::sleep(app.tick_rate), if app.is_loading() => Some(AppEvent::Tick),
+            _ = shutdown.changed() => None,
+        };
+        match app_event {
+            Some(AppEvent::Input(key_event)) => handler.handle_input_event(app, key_event),
+            Some(AppEvent::Mouse(mouse_event)) => handler.handle_mouse_event(app, mouse_event),
+            Some(AppEvent::Update(update)) => update(app),
+            Some(AppEvent::Resize | AppEvent::Tick) => {},
+            // a signal arrived
+            None => break,
         };
 
         if app.closed {
@@ -374,14 +1076,21 @@ pub async fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &Arc<tokio::sy
 }
 
 pub fn ui<B: Backend>(f: &mut Frame<B>, app: &mut App) {
-    let load_ratio = app.get_progress();
-    let is_loading = load_ratio != 1.0;
-
-    let default_style = Style::default().fg(Color::Yellow).bg(Color::Black);
+    let default_style = app.theme.base();
 
     let screen_block = Block::default().style(default_style);
     f.render_widget(screen_block, f.size());
 
+    // the status bar takes the bottom line of the screen
+    let status_divide = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(1)].as_ref())
+        .split(f.size());
+
+    let status_bar = Paragraph::new(app.status_line())
+        .style(default_style.add_modifier(Modifier::REVERSED));
+    f.render_widget(status_bar, status_divide[1]);
+
     let vertical_divide = Layout::default()
         .direction(Direction::Vertical)
         .constraints(
@@ -392,22 +1101,11 @@ pub fn ui<B: Backend>(f: &mut Frame<B>, app: &mut App) {
             .as_ref()
         )
         .margin(5)
-        .split(f.size());
+        .split(status_divide[0]);
 
     let top_bar_area = vertical_divide[0];
     let page_body_area = vertical_divide[1];
 
-    let top_bar_divide = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints(
-            [
-                Constraint::Percentage(80),
-                Constraint::Percentage(20),
-            ]
-            .as_ref()
-        )
-        .split(top_bar_area);
-
         let content_area = Layout::default()
             .direction(Direction::Vertical)
             .constraints(
@@ -419,12 +1117,6 @@ pub fn ui<B: Backend>(f: &mut Frame<B>, app: &mut App) {
             )
             .split(page_body_area);
 
-    let prompt_rect = match is_loading {
-        false => top_bar_area,
-        true => top_bar_divide[0],
-    };
-    let guage_rect = top_bar_divide[1];
-
     let error_display_area = content_area[0];
     let tables_rect = match app.error.len() {
         0 => page_body_area,
@@ -439,59 +1131,275 @@ pub fn ui<B: Backend>(f: &mut Frame<B>, app: &mut App) {
         .style(default_style)
         .block(Block::default().borders(Borders::ALL).title(prompt_title.as_str()));
 
-    f.render_widget(input, prompt_rect);
-
-    if is_loading {
-        let guage = LineGauge::default()
-            .block(
-                Block::default()
-                    .borders(Borders::ALL)
-                    .style(default_style)
-                    .title("Loading")
-            )
-            .gauge_style(default_style.add_modifier(Modifier::ITALIC | Modifier::BOLD))
-            .line_set(symbols::line::THICK)
-            .ratio(load_ratio);
-
-        f.render_widget(guage, guage_rect);
-    }
+    f.render_widget(input, top_bar_area);
+
+    let cursor_x = input_str
+        .chars()
+        .take(app.cursor)
+        .map(|c| c.width().unwrap_or(0) as u16)
+        .sum::<u16>();
+    f.set_cursor(
+        (top_bar_area.x + 1 + cursor_x).min(top_bar_area.right().saturating_sub(2)),
+        top_bar_area.y + 1,
+    );
+    app.regions = Regions {
+        prompt: top_bar_area,
+        ..Regions::default()
+    };
 
-    if app.error.len() > 0 {
+    if !app.error.is_empty() {
         let error_display = Paragraph::new(app.error.as_str())
-            .block(Block::default().title("Error Message").borders(Borders::ALL).style(default_style.fg(Color::Red)))
-            .style(default_style.fg(Color::Red))
+            .block(Block::default().title("Error Message").borders(Borders::ALL).style(app.theme.error_style()))
+            .style(app.theme.error_style())
             .wrap(Wrap { trim: true });
 
         f.render_widget(error_display, error_display_area);
     }
 
-    if app.table_open() {
+    // the auxiliary and participles sit in a panel above the conjugations
+    let tables_rect = match app.verb_info() {
+        Some(verb_info) => {
+            let info_divide = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Length(3), Constraint::Min(0)].as_ref())
+                .split(tables_rect);
+
+            let info_panel = Paragraph::new(verb_info)
+                .style(default_style)
+                .block(Block::default().borders(Borders::ALL).title(app.conjugations.verb.as_str()));
+            f.render_widget(info_panel, info_divide[0]);
+
+            info_divide[1]
+        }
+        None => tables_rect,
+    };
+
+    if app.grid_open() {
+        render_conjugation_grid(f, app, tables_rect, default_style);
+    } else if app.table_open() {
         let reversed_style = default_style.add_modifier(Modifier::REVERSED);
-        let header_cells = app.table_data.header.clone();
-        let header = Row::new(header_cells)
+        let table_data = &app.table_data;
+
+        // columns as wide as their content allows, longer text wrapping onto more lines
+        let widths = table_layout::column_widths(
+            &table_data.header,
+            &table_data.items,
+            tables_rect.width.saturating_sub(2),
+            COLUMN_SPACING,
+        );
+        let wrapped_row = |item: &[String]| {
+            let cells = widths
+                .iter()
+                .enumerate()
+                .map(|(column, width)| table_layout::wrap(item.get(column).map_or("", |c| c.as_str()), *width))
+                .collect::<Vec<Vec<String>>>();
+            let height = cells.iter().map(|lines| lines.len()).max().unwrap_or(1) as u16;
+            (cells, height)
+        };
+
+        let (header_cells, header_height) = wrapped_row(&table_data.header);
+        // tui leaves out a row taller than the space left, so a very long one is cut short
+        let header_height = header_height.min(tables_rect.height.saturating_sub(3).max(1));
+        let body_height = tables_rect.height.saturating_sub(2 + header_height).max(1);
+        let header = Row::new(header_cells.into_iter().map(|lines| lines.join("\n")))
             .style(reversed_style)
-            .height(1);
-        let rows = app.table_data.items
+            .height(header_height);
+        let highlighted_style = app.theme.highlight_style();
+        let highlighted = &table_data.highlighted;
+        // rows from the scrolled to one on, noting where those that fit are so clicks select them
+        let body_top = tables_rect.y + 1 + header_height;
+        let mut row_y = body_top;
+        let mut shown = true; // until a row doesn't fit
+        let rows = table_data.items
             .iter()
-            .map(|item| {
-                let height = 1;
-                let cells = item.iter().map(|c| Cell::from(c.as_str()));
+            .enumerate()
+            .skip(app.table_scroll)
+            .map(|(row, item)| {
+                let (cells, height) = wrapped_row(item);
+                let height = height.min(body_height);
+                shown = shown && row_y + height <= body_top + body_height;
+                if shown {
+                    let area = Rect::new(tables_rect.x + 1, row_y, tables_rect.width.saturating_sub(2), height);
+                    app.regions.rows.push((area, row));
+                    row_y += height;
+                }
+
+                let cells = cells.into_iter().enumerate().map(|(column, lines)| {
+                    let c = lines.join("\n");
+                    match highlighted.contains(&(row, column)) {
+                        true => Cell::from(c).style(highlighted_style),
+                        false => Cell::from(c),
+                    }
+                });
                 Row::new(cells).height(height)
-            });
+            })
+            .collect::<Vec<Row>>();
+
+        // columns that didn't fit the terminal are noted in the title
+        let num_columns = table_data.items
+            .iter()
+            .map(|item| item.len())
+            .chain([table_data.header.len()])
+            .max()
+            .unwrap_or(0);
+        let num_hidden = num_columns.saturating_sub(widths.len());
+        let title = match num_hidden {
+            0 => table_data.title.clone(),
+            _ => format!("{} ({num_hidden} more columns, widen the terminal)", table_data.title),
+        };
+
+        // a numbered tab per table after the title, as far as the border allows
+        let mut title_spans = vec![Span::raw(title.clone())];
+        let num_tables = app.num_tables();
+        if num_tables > 1 {
+            let mut tab_x = tables_rect.x + 1 + table_layout::display_width(&title) + 1;
+            title_spans.push(Span::raw(" "));
+            for index in 0..num_tables {
+                let label = format!(" {} ", index + 1);
+                let label_width = table_layout::display_width(&label);
+                if tab_x + label_width > tables_rect.right().saturating_sub(1) {
+                    break;
+                }
+                app.regions.tabs.push((Rect::new(tab_x, tables_rect.y, label_width, 1), index));
+                title_spans.push(match index == app.current_table {
+                    true => Span::styled(label, reversed_style),
+                    false => Span::raw(label),
+                });
+                tab_x += label_width;
+            }
+        }
+
+        // the selected row is only drawn selected while it is on screen
+        let mut state = TableState::default();
+        state.select(
+            app.regions.rows
+                .iter()
+                .position(|(_, row)| Some(*row) == app.selected_row)
+        );
+        let widths = widths.into_iter().map(Constraint::Length).collect::<Vec<Constraint>>();
 
         let current_conjugation_table = Table::new(rows)
             .header(header)
             .block(
                 Block::default()
                     .borders(Borders::ALL)
-                    .title((&app.table_data.title).clone())
+                    .title(Spans::from(title_spans))
             )
-            .widths(&[
-                Constraint::Percentage(20),
-                Constraint::Percentage(80),
-            ])
+            .widths(&widths)
+            .column_spacing(COLUMN_SPACING)
+            .highlight_style(reversed_style)
+            .style(default_style);
+
+        f.render_stateful_widget(current_conjugation_table, tables_rect, &mut state);
+    }
+}
+
+/// Cells between two columns of a table.
+const COLUMN_SPACING: u16 = 1;
+
+/// Lays out every tense of the current verb, one bordered section per mood
+/// with as many tense tables per line as fit the terminal width.
+fn render_conjugation_grid<B: Backend>(f: &mut Frame<B>, app: &App, area: Rect, default_style: Style) {
+    let min_table_width = 28;
+    let num_columns = max(1, area.width / min_table_width) as usize;
+    let reversed_style = default_style.add_modifier(Modifier::REVERSED);
+    let irregular_style = app.theme.highlight_style();
+
+    let verb = &app.conjugations.verb;
+    let language = &app.conjugations.language;
+    let grid_block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(
+            "{}: all tenses {language}{}{}",
+            app.verb_heading(),
+            app.variant_note(),
+            app.generated_note(),
+        ))
+        .style(default_style);
+    let grid_area = grid_block.inner(area);
+    let conjugator = RegularConjugator::new(language, verb);
+    f.render_widget(grid_block, area);
+
+    let mut y = grid_area.y;
+    for (mood_label, tables) in app.conjugations.tables_by_mood().into_iter().skip(app.grid_scroll) {
+        let lines = tables.chunks(num_columns).collect::<Vec<_>>();
+        let line_heights = lines
+            .iter()
+            .map(|line| {
+                let rows = line.iter().map(|table| table.forms.len()).max().unwrap_or(0);
+                rows as u16 + 2
+            })
+            .collect::<Vec<u16>>();
+        let section_height = line_heights.iter().sum::<u16>() + 2;
+
+        let remaining = grid_area.bottom().saturating_sub(y);
+        if remaining < 5 {
+            break;
+        }
+
+        let section_area = Rect::new(grid_area.x, y, grid_area.width, section_height.min(remaining));
+        let section_block = Block::default()
+            .borders(Borders::ALL)
+            .title(mood_label)
             .style(default_style);
+        let section_inner = section_block.inner(section_area);
+        let mut line_y = section_inner.y;
+        f.render_widget(section_block, section_area);
+
+        for (line, line_height) in lines.iter().zip(line_heights) {
+            let line_height = line_height.min(section_inner.bottom().saturating_sub(line_y));
+            if line_height < 3 {
+                break;
+            }
+
+            let line_area = Rect::new(section_inner.x, line_y, section_inner.width, line_height);
+            let columns = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints(vec![Constraint::Ratio(1, num_columns as u32); num_columns])
+                .split(line_area);
+
+            for (table, column) in line.iter().zip(columns) {
+                let table_rows = app.table_rows(table);
+                let pronoun_width = table_rows
+                    .iter()
+                    .map(|row| table_layout::display_width(&row[0]))
+                    .max()
+                    .unwrap_or(0);
+                let irregular_rows = conjugator
+                    .as_ref()
+                    .map(|conjugator| conjugator.irregular_rows(table))
+                    .unwrap_or_default();
+                let rows = table_rows
+                    .iter()
+                    .enumerate()
+                    .map(|(row, item)| {
+                        let form_cell = match irregular_rows.contains(&row) {
+                            true => Cell::from(item[1].as_str()).style(irregular_style),
+                            false => Cell::from(item[1].as_str()),
+                        };
+                        Row::new(vec![Cell::from(item[0].as_str()), form_cell])
+                    });
+
+                let widths = [
+                    Constraint::Length(pronoun_width),
+                    Constraint::Percentage(100),
+                ];
+
+                let tense_table = Table::new(rows)
+                    .block(
+                        Block::default()
+                            .borders(Borders::ALL)
+                            .title(Span::styled(table.tense.as_str(), reversed_style))
+                    )
+                    .widths(&widths)
+                    .style(default_style);
+
+                f.render_widget(tense_table, column);
+            }
+
+            line_y += line_height;
+        }
 
-        f.render_stateful_widget(current_conjugation_table, tables_rect, &mut app.state);
+        y += section_height;
     }
 }
@@ -0,0 +1,66 @@
+use std::fmt;
+
+use crate::{http::FetchError, user_error::UserError};
+
+/// Why a lookup or the setup failed, shown to the user as a `UserError`.
+#[derive(Clone, Debug)]
+pub enum AppError {
+    Network(FetchError),
+    Parse { message: String }, // a page not laid out as expected
+    NotFound { message: String }, // a word or verb wordreference doesn't know
+    Cache { message: String }, // reading or writing the sqlite database
+    Config { message: String }, // an invalid setting, flag or variable
+}
+
+impl AppError {
+    /// Whether wordreference couldn't be reached at all, as opposed to
+    /// answering with an error or an unexpected page.
+    pub fn is_unreachable(&self) -> bool {
+        match self {
+            AppError::Network(err) => err.is_unreachable(),
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AppError::Network(err) => write!(f, "{err}"),
+            AppError::Parse { message } => write!(f, "Could not read the page: {message}"),
+            AppError::NotFound { message } => write!(f, "{message}"),
+            AppError::Cache { message } => write!(f, "The cache failed: {message}"),
+            AppError::Config { message } => write!(f, "{message}"),
+        }
+    }
+}
+
+impl From<AppError> for UserError {
+    fn from(err: AppError) -> Self {
+        UserError {
+            message: err.to_string(),
+        }
+    }
+}
+
+impl From<FetchError> for AppError {
+    fn from(err: FetchError) -> Self {
+        AppError::Network(err)
+    }
+}
+
+impl From<rusqlite::Error> for AppError {
+    fn from(err: rusqlite::Error) -> Self {
+        AppError::Cache {
+            message: err.to_string(),
+        }
+    }
+}
+
+impl From<serde_json::Error> for AppError {
+    fn from(err: serde_json::Error) -> Self {
+        AppError::Cache {
+            message: err.to_string(),
+        }
+    }
+}
@@ -1,104 +1,99 @@
-use std::{time::Duration, sync::Arc, process::exit};
-use crossterm::event::{KeyEvent, self, KeyCode};
-use tokio::sync::mpsc::{channel, Receiver, Sender};
+use std::time::Duration;
+use crossterm::event::{KeyEvent, self, KeyCode, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+use tokio::sync::mpsc::Sender;
 
-use crate::app::App;
+use crate::{app::App, config::KeyBindings};
 
-#[derive(Copy, Clone)]
+/// Everything the app reacts to, applied in the order it arrives.
 pub enum AppEvent {
     Input(KeyEvent),
-    Tick,
+    Mouse(MouseEvent), // clicks and the scroll wheel
+    Resize,
+    Tick, // only sent while the loading gauge animates
+    Update(AppUpdate),
 }
 
-pub struct AppEvents {
-    rx: Receiver<AppEvent>,
-    _tx: Sender<AppEvent>,
-}
+/// A change to the app made on behalf of a lookup.
+pub type AppUpdate = Box<dyn FnOnce(&mut App) + Send>;
 
-impl AppEvents {
-    pub fn new(tick_rate: Duration) -> AppEvents {
-        let (tx, rx) = channel(512);
+/// Sends key presses, clicks, scrolling and resizes until the terminal fails or the app stops
+/// receiving. Polling blocks, so it gets a thread rather than a runtime worker.
+pub fn read_terminal(tx: Sender<AppEvent>, poll_interval: Duration) {
+    std::thread::spawn(move || {
+        loop {
+            let app_event = match crossterm::event::poll(poll_interval) {
+                Ok(true) => match event::read() {
+                    Ok(event::Event::Key(key)) => AppEvent::Input(key),
+                    // moves and drags would only redraw for nothing
+                    Ok(event::Event::Mouse(mouse)) => match mouse.kind {
+                        MouseEventKind::Down(MouseButton::Left)
+                        | MouseEventKind::ScrollUp
+                        | MouseEventKind::ScrollDown => AppEvent::Mouse(mouse),
+                        _ => continue,
+                    },
+                    Ok(event::Event::Resize(_, _)) => AppEvent::Resize,
+                    Err(_) => break,
+                },
+                // nothing typed, only checking the app is still there
+                Ok(false) if tx.is_closed() => break,
+                Ok(false) => continue,
+                Err(_) => break,
+            };
 
-        let event_tx = tx.clone(); // the thread::spawn own event_tx 
-        tokio::spawn(async move {
-            loop {
-                // poll for tick rate duration, if no event, sent tick event.
-                if crossterm::event::poll(tick_rate).unwrap() {
-                    let event = event::read().unwrap();
-                    if let event::Event::Key(key) = event {
-                        let key = KeyEvent::from(key);
-                        if let Err(err) = event_tx.send(AppEvent::Input(key)).await {
-                            println!("Send App Input Failed, connection is closed? {}", err);
-                            exit(1);
-                        }
-                    } else {
-                        if let Err(err) = event_tx.send(AppEvent::Tick).await {
-                            println!("Send App Tick Failed, connection is closed? {}", err);
-                            exit(1);
-                        }
-                    }
-                } else {
-                    if let Err(err) = event_tx.send(AppEvent::Tick).await {
-                        println!("Send App Tick Failed, connection is closed? {}", err);
-                        exit(1);
-                    }
-                }
+            if tx.blocking_send(app_event).is_err() {
+                break;
             }
-        });
-
-        AppEvents {
-            rx,
-            _tx: tx
         }
-    }
-
-    /// Attempts to read an event.
-    /// This function block the current thread.
-    pub async fn next(&mut self) -> Option<AppEvent> {
-        self.rx.recv().await
-    }
+    });
 }
 
 pub struct AppEventHandler {
-    app: Arc<tokio::sync::Mutex<App>>,
+    keys: KeyBindings,
 }
 
 impl AppEventHandler {
-    pub fn new(app: Arc<tokio::sync::Mutex<App>>) -> Self {
-        Self { app }
+    pub fn new(keys: KeyBindings) -> Self {
+        Self { keys }
     }
 
-    pub async fn handle_app_event(&mut self, app_event: AppEvent) {
-        match app_event {
-            AppEvent::Input(chr) => {
-                self.handle_input_event(chr).await;
-            },
-            AppEvent::Tick => {
-                self.update_on_tick().await;
-            },
-        };
-    }
-
-    async fn update_on_tick(&mut self) {
-        let mut _app = self.app.lock().await;
-    }
-
-    async fn handle_input_event(&mut self, input: KeyEvent) {
-        let mut app = self.app.lock().await;
+    pub fn handle_input_event(&self, app: &mut App, input: KeyEvent) {
+        let keys = &self.keys;
 
         match input.code {
-            KeyCode::Esc => app.close(),
-            KeyCode::Right => {
+            // quitting and Ctrl+C cancel a lookup in progress, and otherwise quit
+            _ if keys.quit.matches(&input)
+                || (input.code == KeyCode::Char('c') && input.modifiers.contains(KeyModifiers::CONTROL)) => {
+                match app.is_loading() {
+                    true => app.cancel_lookups(),
+                    false => app.close(),
+                }
+            }
+            _ if keys.next_table.matches(&input) => {
                 app.next();
             }
-            KeyCode::Left => {
+            _ if keys.prev_table.matches(&input) => {
                 app.prev();
             }
+            _ if keys.scroll_up.matches(&input) => {
+                app.scroll_up();
+            }
+            _ if keys.scroll_down.matches(&input) => {
+                app.scroll_down();
+            }
+            _ if keys.all_tenses.matches(&input) => {
+                app.toggle_all_tenses();
+            }
+            _ if keys.negative.matches(&input) => {
+                app.toggle_negative();
+            }
+            _ if keys.interrogative.matches(&input) => {
+                app.toggle_interrogative();
+            }
             KeyCode::Backspace => {
                 app.pop_char();
             }
             KeyCode::Enter => {
-                app.handle_entry().await;
+                app.handle_entry();
             }
             KeyCode::Char(c) => {
                 app.put_char(c);
@@ -106,4 +101,13 @@ impl AppEventHandler {
             _ => {}
         }
     }
-}
\ No newline at end of file
+
+    pub fn handle_mouse_event(&self, app: &mut App, mouse: MouseEvent) {
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => app.click(mouse.column, mouse.row),
+            MouseEventKind::ScrollUp => app.scroll_up(),
+            MouseEventKind::ScrollDown => app.scroll_down(),
+            _ => {}
+        }
+    }
+}
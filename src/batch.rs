@@ -0,0 +1,173 @@
+use std::{
+    collections::VecDeque,
+    fs,
+    sync::{atomic::{AtomicUsize, Ordering}, Arc},
+    time::Duration,
+};
+
+use rusqlite::Connection;
+use tokio::sync::{mpsc::channel, Mutex};
+
+use crate::{
+    config::Config,
+    http::HttpClient,
+    lookup_event::LookupEventHandler,
+    user_error::UserError,
+    wordreference::wordreference_utils,
+};
+
+pub struct BatchOptions {
+    pub path: String,
+    pub kind: String, // "conj", "def" or "trans"
+    pub language: String,
+    pub native_language: String, // the other side of def and trans
+    pub concurrency: usize,
+    pub delay: Duration, // between the lookups of each worker
+}
+
+impl BatchOptions {
+    /// Parses the arguments following `batch`, e.g.
+    /// "words.txt --kind conj --lang fr --concurrency 4 --delay 250".
+    pub fn parse(args: &[String], config: &Config) -> Result<BatchOptions, UserError> {
+        let usage = || UserError {
+            message: "Usage: transrustate batch <file> [--kind conj|def|trans] [--lang <language>] \
+            [--concurrency <lookups>] [--delay <milliseconds>]".to_string(),
+        };
+
+        let mut options = BatchOptions {
+            path: String::new(),
+            kind: "conj".to_string(),
+            language: config.defaults.language.clone(),
+            native_language: config.defaults.native_language.clone(),
+            concurrency: 2,
+            delay: Duration::from_millis(500),
+        };
+
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--kind" => {
+                    options.kind = args.next().ok_or_else(usage)?.to_lowercase();
+                    if !matches!(options.kind.as_str(), "conj" | "def" | "trans") {
+                        return Err(usage());
+                    }
+                }
+                "--lang" => {
+                    let language = args.next().ok_or_else(usage)?;
+                    options.language = wordreference_utils::parse_language(language)
+                        .filter(|language| *language != options.native_language)
+                        .ok_or_else(|| UserError {
+                            message: format!(
+                                "Unsupported language '{language}', try one other than {}",
+                                options.native_language,
+                            ),
+                        })?;
+                }
+                "--concurrency" => {
+                    options.concurrency = args.next()
+                        .and_then(|concurrency| concurrency.parse().ok())
+                        .filter(|concurrency| *concurrency > 0)
+                        .ok_or_else(usage)?;
+                }
+                "--delay" => {
+                    options.delay = args.next()
+                        .and_then(|delay| delay.parse().ok())
+                        .map(Duration::from_millis)
+                        .ok_or_else(usage)?;
+                }
+                _ if arg.starts_with("--") || !options.path.is_empty() => return Err(usage()),
+                _ => options.path = arg.clone(),
+            }
+        }
+
+        match options.path.is_empty() {
+            true => Err(usage()),
+            false => Ok(options),
+        }
+    }
+}
+
+/// The entries of a word list, one per line, skipping blank lines and
+/// "#" comments.
+fn read_entries(path: &str) -> Result<Vec<String>, UserError> {
+    let contents = fs::read_to_string(path).map_err(|err| UserError {
+        message: format!("Could not read '{path}': {err}"),
+    })?;
+
+    Ok(contents
+        .lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| line.to_string())
+        .collect())
+}
+
+/// Looks up every entry of a word list to fill the cache, printing how each
+/// went. Returns the number of failed entries.
+pub async fn run_batch(
+    options: BatchOptions,
+    client: HttpClient,
+    connection: Connection,
+) -> Result<usize, UserError> {
+    let entries = read_entries(&options.path)?;
+    let num_entries = entries.len();
+    let queue = Arc::new(Mutex::new(entries.into_iter().collect::<VecDeque<String>>()));
+    let options = Arc::new(options);
+
+    // the lookup handler reports to an app, which there is none of here
+    let (app_tx, _) = channel(1);
+
+    // counted as they succeed, so whatever a panicking worker was doing or
+    // left in the queue counts as failed
+    let num_succeeded = Arc::new(AtomicUsize::new(0));
+
+    // workers share a handler, so repeated entries share one fetch
+    let handler = Arc::new(LookupEventHandler::new(app_tx, client, connection));
+    let workers = (0..options.concurrency.min(num_entries))
+        .map(|_| {
+            let queue = Arc::clone(&queue);
+            let options = Arc::clone(&options);
+            let num_succeeded = Arc::clone(&num_succeeded);
+            let handler = Arc::clone(&handler);
+
+            tokio::spawn(async move {
+                loop {
+                    let Some(entry) = queue.lock().await.pop_front() else {
+                        break;
+                    };
+
+                    match handler.warm_cache(&options.kind, &entry, &options.language, &options.native_language).await {
+                        Ok(cached) => {
+                            num_succeeded.fetch_add(1, Ordering::SeqCst);
+                            match cached.is_empty() || cached == entry {
+                                true => println!("ok      {entry}"),
+                                false => println!("ok      {entry} ({cached})"),
+                            }
+                        }
+                        Err(err) => println!("failed  {entry}: {err}"),
+                    }
+
+                    if queue.lock().await.is_empty() {
+                        break;
+                    }
+                    tokio::time::sleep(options.delay).await;
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+
+    for worker in workers {
+        if let Err(err) = worker.await {
+            println!("failed  a lookup worker stopped: {err}");
+        }
+    }
+
+    let num_failed = num_entries - num_succeeded.load(Ordering::SeqCst);
+    println!(
+        "{} of {num_entries} {} lookups succeeded, {num_failed} failed",
+        num_entries - num_failed,
+        options.kind,
+    );
+
+    Ok(num_failed)
+}
@@ -0,0 +1,164 @@
+use crate::{
+    conjugations::{ConjugationTable, VerbConjugations},
+    conjugator::{self, RegularConjugator, VerbClass},
+    grammar::{Mood, Person, Tense},
+    verb_models,
+};
+
+/// A verb's conjugation group along with the model verb it follows, if any.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Classification {
+    pub group: String,
+    pub model: Option<String>,
+}
+
+impl Classification {
+    pub fn describe(&self) -> String {
+        match &self.model {
+            Some(model) => format!("{}, conjugates like {model}", self.group),
+            None => self.group.clone(),
+        }
+    }
+}
+
+/// Spanish stem changes, seen in the stressed forms of the present.
+#[derive(Clone, Copy)]
+enum StemChange {
+    EToIe,
+    OToUe,
+    UToUe,
+    EToI,
+}
+
+impl StemChange {
+    const ALL: [StemChange; 4] = [
+        StemChange::EToIe,
+        StemChange::OToUe,
+        StemChange::UToUe,
+        StemChange::EToI,
+    ];
+
+    fn vowels(&self) -> (&'static str, &'static str) {
+        match self {
+            StemChange::EToIe => ("e", "ie"),
+            StemChange::OToUe => ("o", "ue"),
+            StemChange::UToUe => ("u", "ue"),
+            StemChange::EToI => ("e", "i"),
+        }
+    }
+
+    /// Whether "tiene" is the stem "ten" with this change applied.
+    fn applies(&self, stem: &str, form: &str) -> bool {
+        let (from, to) = self.vowels();
+        match stem.rfind(from) {
+            Some(position) => {
+                let changed = format!("{}{to}{}", &stem[..position], &stem[position + from.len()..]);
+                form.starts_with(&changed)
+            }
+            None => false,
+        }
+    }
+
+    fn model(&self, class: VerbClass) -> &'static str {
+        match (self, class) {
+            (StemChange::EToIe, VerbClass::SpanishAr) => "pensar",
+            (StemChange::EToIe, VerbClass::SpanishEr) => "entender",
+            (StemChange::EToIe, _) => "sentir",
+            (StemChange::OToUe, VerbClass::SpanishAr) => "contar",
+            (StemChange::OToUe, VerbClass::SpanishEr) => "volver",
+            (StemChange::OToUe, _) => "dormir",
+            (StemChange::UToUe, _) => "jugar",
+            (StemChange::EToI, _) => "pedir",
+        }
+    }
+}
+
+fn present_table(conjugations: &VerbConjugations) -> Option<&ConjugationTable> {
+    conjugations.conjugation_tables
+        .iter()
+        .find(|table| table.mood == Mood::Indicative && table.tense_kind == Tense::Present)
+}
+
+/// The first form listed for a person, e.g. "paie" for "paie, paye".
+fn present_form(conjugations: &VerbConjugations, person: Person) -> Option<String> {
+    present_table(conjugations)?
+        .forms
+        .iter()
+        .find(|form| form.person == Some(person))
+        .and_then(|form| form.form.split(',').next())
+        .map(|form| form.trim().to_lowercase())
+}
+
+/// The last letters of an infinitive, e.g. "ar" for "hablar".
+fn ending(infinitive: &str, num_letters: usize) -> &str {
+    infinitive
+        .char_indices()
+        .rev()
+        .nth(num_letters - 1)
+        .map(|(position, _)| &infinitive[position..])
+        .unwrap_or(infinitive)
+}
+
+/// Classifies a conjugated verb from its infinitive and present tense.
+pub fn classify(conjugations: &VerbConjugations, language: &str) -> Option<Classification> {
+    let infinitive = conjugator::base_infinitive(language, &conjugations.verb);
+    let regular = RegularConjugator::new(language, &infinitive);
+    let first_singular = present_form(conjugations, Person::FirstSingular).unwrap_or_default();
+    let third_singular = present_form(conjugations, Person::ThirdSingular).unwrap_or_default();
+    let first_plural = present_form(conjugations, Person::FirstPlural).unwrap_or_default();
+
+    let stem_change = match &regular {
+        Some(regular) if language == "spanish" => StemChange::ALL
+            .into_iter()
+            .find(|change| change.applies(&regular.stem, &third_singular)),
+        _ => None,
+    };
+
+    let group = match language {
+        "french" if infinitive.ends_with("er") && infinitive != "aller" => "1er groupe".to_string(),
+        "french" if infinitive.ends_with("ir") && first_plural.ends_with("issons") => "2e groupe".to_string(),
+        "french" => "3e groupe".to_string(),
+        "spanish" => {
+            let ending = ending(&infinitive, 2);
+            match stem_change {
+                Some(change) => {
+                    let (from, to) = change.vowels();
+                    format!("-{ending} verb, stem-changing {from}→{to}")
+                }
+                None => format!("-{ending} verb"),
+            }
+        }
+        "italian" if infinitive.ends_with("ire") && first_singular.ends_with("isco") => "-ire verb (-isc-)".to_string(),
+        "italian" => format!("-{} verb", ending(&infinitive, 3)),
+        _ => return None,
+    };
+
+    let model = if let Some((_, model)) = verb_models::find_model(language, &infinitive) {
+        Some(model.infinitive)
+    } else if let (Some(change), Some(regular)) = (stem_change, &regular) {
+        Some(change.model(regular.class))
+    } else {
+        // only verbs following their class's pattern throughout the present
+        // share its model, "courir" is in -ir but not like "finir"
+        regular
+            .filter(|regular| present_table(conjugations)
+                .is_some_and(|table| regular.irregular_rows(table).is_empty()))
+            .map(|regular| match regular.class {
+                VerbClass::FrenchEr => "parler",
+                VerbClass::FrenchIr => "finir",
+                VerbClass::FrenchRe => "vendre",
+                VerbClass::SpanishAr => "hablar",
+                VerbClass::SpanishEr => "comer",
+                VerbClass::SpanishIr => "vivir",
+                VerbClass::ItalianAre => "parlare",
+                VerbClass::ItalianEre => "credere",
+                VerbClass::ItalianIre if first_singular.ends_with("isco") => "finire",
+                VerbClass::ItalianIre => "dormire",
+            })
+    };
+
+    Some(Classification {
+        group,
+        model: model.map(|model| model.to_string()),
+    })
+}
@@ -0,0 +1,124 @@
+use crate::{
+    conjugations::{ConjugationTable, VerbConjugations},
+    grammar::{self, Person, Tense},
+    wordreference::wordreference_utils,
+};
+
+/// A conjugated verb along with the language it was looked up in.
+pub struct ComparedVerb {
+    pub conjugations: VerbConjugations,
+    pub language: String,
+}
+
+impl ComparedVerb {
+    pub fn heading(&self) -> String {
+        let language_code = wordreference_utils::map_language(self.language.clone());
+        format!("{} ({language_code})", self.conjugations.verb)
+    }
+}
+
+pub struct ComparisonTable {
+    pub title: String,
+    pub header: Vec<String>,
+    pub items: Vec<Vec<String>>,
+    pub differing: Vec<usize>, // rows whose forms differ
+}
+
+/// Two verbs with their tenses paired up by meaning rather than position,
+/// so "présent" lines up with "presente" across languages.
+pub struct Comparison {
+    pub left: ComparedVerb,
+    pub right: ComparedVerb,
+    pub pairs: Vec<(usize, usize)>,
+}
+
+impl Comparison {
+    pub fn new(left: ComparedVerb, right: ComparedVerb) -> Comparison {
+        let right_tables = &right.conjugations.conjugation_tables;
+
+        let mut pairs: Vec<(usize, usize)> = Vec::new();
+        for (left_index, left_table) in left.conjugations.conjugation_tables.iter().enumerate() {
+            let right_index = right_tables
+                .iter()
+                .enumerate()
+                .filter(|(right_index, _)| !pairs.iter().any(|(_, paired)| paired == right_index))
+                .find(|(_, right_table)| Comparison::tenses_match(left_table, right_table))
+                .map(|(right_index, _)| right_index);
+
+            if let Some(right_index) = right_index {
+                pairs.push((left_index, right_index));
+            }
+        }
+
+        Comparison {
+            left,
+            right,
+            pairs,
+        }
+    }
+
+    fn tenses_match(left: &ConjugationTable, right: &ConjugationTable) -> bool {
+        match left.tense_kind {
+            Tense::Unknown => grammar::fold(&left.label()) == grammar::fold(&right.label()),
+            tense_kind => left.mood == right.mood && tense_kind == right.tense_kind,
+        }
+    }
+
+    pub fn table(&self, pair_index: usize) -> Option<ComparisonTable> {
+        let (left_index, right_index) = *self.pairs.get(pair_index)?;
+        let left_table = &self.left.conjugations.conjugation_tables[left_index];
+        let right_table = &self.right.conjugations.conjugation_tables[right_index];
+
+        let same_language = self.left.language == self.right.language;
+        let mut items: Vec<Vec<String>> = Vec::new();
+        let mut differing: Vec<usize> = Vec::new();
+
+        for person in Person::ALL {
+            let left_form = left_table.forms.iter().find(|form| form.person == Some(person));
+            let right_form = right_table.forms.iter().find(|form| form.person == Some(person));
+            if left_form.is_none() && right_form.is_none() {
+                continue;
+            }
+
+            let pronoun = match (left_form, right_form) {
+                (Some(left), Some(right)) if !same_language => format!("{} / {}", left.pronoun, right.pronoun),
+                (Some(form), _) | (_, Some(form)) => form.pronoun.clone(),
+                (None, None) => String::new(),
+            };
+            let left_text = left_form.map(|form| form.form.clone()).unwrap_or_default();
+            let right_text = right_form.map(|form| form.form.clone()).unwrap_or_default();
+
+            if left_text != right_text {
+                differing.push(items.len());
+            }
+            items.push(vec![pronoun, left_text, right_text]);
+        }
+
+        let title = match same_language {
+            true => format!(
+                "{} vs {}: {}",
+                self.left.conjugations.verb,
+                self.right.conjugations.verb,
+                left_table.label(),
+            ),
+            false => format!(
+                "{} vs {}: {} / {}",
+                self.left.heading(),
+                self.right.heading(),
+                left_table.label(),
+                right_table.label(),
+            ),
+        };
+
+        Some(ComparisonTable {
+            title,
+            header: vec![
+                "Pronouns".to_string(),
+                self.left.heading(),
+                self.right.heading(),
+            ],
+            items,
+            differing,
+        })
+    }
+}
@@ -0,0 +1,613 @@
+use std::{collections::BTreeMap, fmt, fs, path::{Path, PathBuf}, time::Duration};
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::{de::Error, Deserialize, Deserializer};
+use tui::style::{Color, Modifier, Style};
+
+use crate::{
+    app_error::AppError,
+    http::{HttpConfig, HttpOptions},
+    wordreference::wordreference_utils,
+};
+
+/// Settings read from `$XDG_CONFIG_HOME/transrustate/config.toml`, every
+/// one of them optional.
+#[derive(Deserialize, Clone, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub defaults: Defaults,
+    pub cache: CacheConfig,
+    pub ui: UiConfig,
+    pub theme: ThemeConfig,
+    pub network: NetworkConfig,
+    pub keys: KeyBindings,
+}
+
+#[derive(Deserialize, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct Defaults {
+    #[serde(deserialize_with = "language")]
+    pub language: String, // the language words are looked up in
+    #[serde(deserialize_with = "language")]
+    pub native_language: String, // the other side of def and trans
+}
+
+impl Default for Defaults {
+    fn default() -> Self {
+        Self {
+            language: "french".to_string(),
+            native_language: "english".to_string(),
+        }
+    }
+}
+
+#[derive(Deserialize, Clone, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct CacheConfig {
+    pub path: Option<PathBuf>, // "~/" is expanded
+}
+
+impl CacheConfig {
+    /// The database lookups are cached in: the `--db` flag, then the
+    /// TRANSRUSTATE_DB environment variable, then the config file, then
+    /// `$XDG_DATA_HOME/transrustate/cache.db`. ":memory:" keeps the cache
+    /// in memory for the session.
+    pub fn db_path(&self, flag: Option<PathBuf>) -> Result<PathBuf, AppError> {
+        let chosen = flag
+            .or_else(|| std::env::var_os("TRANSRUSTATE_DB").filter(|path| !path.is_empty()).map(PathBuf::from))
+            .or_else(|| self.path.clone());
+        if let Some(path) = chosen {
+            return Ok(match path.as_os_str() == IN_MEMORY_DB {
+                true => path,
+                false => expand_home(&path),
+            });
+        }
+
+        let data_home = std::env::var_os("XDG_DATA_HOME")
+            .map(PathBuf::from)
+            .filter(|path| path.is_absolute())
+            .or_else(|| home::home_dir().map(|home| home.join(".local").join("share")))
+            .ok_or_else(|| AppError::Config {
+                message: "Could not find a home directory for the cache, \
+                set XDG_DATA_HOME, TRANSRUSTATE_DB or --db".to_string(),
+            })?;
+
+        let path = data_home.join("transrustate").join("cache.db");
+        migrate_legacy_db(&path)?;
+        Ok(path)
+    }
+}
+
+/// The path sqlite opens as a throwaway database.
+pub const IN_MEMORY_DB: &str = ":memory:";
+
+/// Moves the cache from `~/.lang_rs.db`, where earlier versions kept it, to
+/// the default location, unless a cache is already there.
+fn migrate_legacy_db(path: &Path) -> Result<(), AppError> {
+    let Some(legacy) = home::home_dir().map(|home| home.join(".lang_rs.db")) else {
+        return Ok(());
+    };
+    if path.exists() || !legacy.is_file() {
+        return Ok(());
+    }
+
+    let migrate_error = |err: std::io::Error| AppError::Cache {
+        message: format!("could not move {} to {}, {err}", legacy.display(), path.display()),
+    };
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(migrate_error)?;
+    }
+
+    // renaming fails across file systems, where copying does not
+    if fs::rename(&legacy, path).is_err() {
+        fs::copy(&legacy, path).map_err(migrate_error)?;
+        fs::remove_file(&legacy).map_err(migrate_error)?;
+    }
+    Ok(())
+}
+
+#[derive(Deserialize, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct UiConfig {
+    pub tick_rate_ms: u64, // how often the status bar updates while loading
+}
+
+impl Default for UiConfig {
+    fn default() -> Self {
+        Self {
+            tick_rate_ms: 200,
+        }
+    }
+}
+
+/// The themes `[theme] name` and the theme command choose from.
+pub const THEME_NAMES: [&str; 5] = ["dark", "light", "solarized", "high-contrast", "monochrome"];
+
+/// How the app is drawn, either in colors or, for terminals and readers
+/// without them, in bold and reverse only.
+#[derive(Clone)]
+pub struct Theme {
+    pub foreground: Color,
+    pub background: Color,
+    pub error: Color,
+    pub highlight: Color, // irregular forms and quiz verdicts
+    pub monochrome: bool,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme::named("dark").expect("dark is a theme")
+    }
+}
+
+impl Theme {
+    pub fn named(name: &str) -> Option<Theme> {
+        let colors = |foreground, background, error, highlight| Theme {
+            foreground,
+            background,
+            error,
+            highlight,
+            monochrome: false,
+        };
+
+        let theme = match name {
+            "dark" => colors(Color::Yellow, Color::Black, Color::Red, Color::Cyan),
+            "light" => colors(Color::Black, Color::White, Color::Red, Color::Blue),
+            "solarized" => colors(
+                Color::Rgb(0x83, 0x94, 0x96),
+                Color::Rgb(0x00, 0x2b, 0x36),
+                Color::Rgb(0xdc, 0x32, 0x2f),
+                Color::Rgb(0xb5, 0x89, 0x00),
+            ),
+            "high-contrast" => colors(Color::White, Color::Black, Color::LightRed, Color::LightYellow),
+            "monochrome" => Theme {
+                monochrome: true,
+                ..colors(Color::Reset, Color::Reset, Color::Reset, Color::Reset)
+            },
+            _ => return None,
+        };
+        Some(theme)
+    }
+
+    /// Text, borders and the screen behind them.
+    pub fn base(&self) -> Style {
+        match self.monochrome {
+            true => Style::default(),
+            false => Style::default().fg(self.foreground).bg(self.background),
+        }
+    }
+
+    pub fn error_style(&self) -> Style {
+        match self.monochrome {
+            true => self.base().add_modifier(Modifier::BOLD),
+            false => self.base().fg(self.error),
+        }
+    }
+
+    pub fn highlight_style(&self) -> Style {
+        match self.monochrome {
+            true => self.base().add_modifier(Modifier::BOLD | Modifier::REVERSED),
+            false => self.base().fg(self.highlight).add_modifier(Modifier::BOLD),
+        }
+    }
+}
+
+/// A named theme, colors of which can be overridden by name, e.g.
+/// "lightblue", or as "#rrggbb".
+#[derive(Deserialize, Clone, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct ThemeConfig {
+    #[serde(deserialize_with = "theme_name")]
+    pub name: Option<String>,
+    #[serde(deserialize_with = "color")]
+    pub foreground: Option<Color>,
+    #[serde(deserialize_with = "color")]
+    pub background: Option<Color>,
+    #[serde(deserialize_with = "color")]
+    pub error: Option<Color>,
+    #[serde(deserialize_with = "color")]
+    pub highlight: Option<Color>,
+}
+
+impl ThemeConfig {
+    /// The configured theme, monochrome when NO_COLOR is set and no theme
+    /// is named.
+    pub fn theme(&self) -> Theme {
+        let no_color = std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+        let name = match (&self.name, no_color) {
+            (Some(name), _) => name.as_str(),
+            (None, true) => "monochrome",
+            (None, false) => "dark",
+        };
+
+        let theme = Theme::named(name).unwrap_or_default();
+        Theme {
+            foreground: self.foreground.unwrap_or(theme.foreground),
+            background: self.background.unwrap_or(theme.background),
+            error: self.error.unwrap_or(theme.error),
+            highlight: self.highlight.unwrap_or(theme.highlight),
+            ..theme
+        }
+    }
+}
+
+/// Overridden by the TRANSRUSTATE_* environment variables of [`HttpConfig`].
+#[derive(Deserialize, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct NetworkConfig {
+    pub proxy: Option<String>,
+    pub user_agent: Option<String>,
+    pub headers: BTreeMap<String, String>,
+    pub base_url: Option<String>,
+    pub timeout_secs: u64,
+    pub connect_timeout_secs: u64,
+    pub max_retries: u32,
+    pub initial_backoff_ms: u64,
+    pub min_interval_ms: u64,
+}
+
+impl Default for NetworkConfig {
+    fn default() -> Self {
+        let options = HttpOptions::default();
+        Self {
+            proxy: None,
+            user_agent: None,
+            headers: BTreeMap::new(),
+            base_url: None,
+            timeout_secs: options.timeout.as_secs(),
+            connect_timeout_secs: options.connect_timeout.as_secs(),
+            max_retries: options.max_retries,
+            initial_backoff_ms: options.initial_backoff.as_millis() as u64,
+            min_interval_ms: options.min_interval.as_millis() as u64,
+        }
+    }
+}
+
+impl NetworkConfig {
+    pub fn http_config(&self) -> HttpConfig {
+        HttpConfig {
+            proxy: self.proxy.clone(),
+            user_agent: self.user_agent.clone(),
+            headers: self.headers.clone().into_iter().collect(),
+            base_url: self.base_url.clone(),
+            options: HttpOptions {
+                timeout: Duration::from_secs(self.timeout_secs),
+                connect_timeout: Duration::from_secs(self.connect_timeout_secs),
+                max_retries: self.max_retries,
+                initial_backoff: Duration::from_millis(self.initial_backoff_ms),
+                min_interval: Duration::from_millis(self.min_interval_ms),
+            },
+        }
+    }
+}
+
+/// A key along with the modifiers held, e.g. "f2" or "ctrl+n".
+#[derive(Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(try_from = "String")]
+pub struct KeyBinding {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl KeyBinding {
+    const fn key(code: KeyCode) -> KeyBinding {
+        KeyBinding {
+            code,
+            modifiers: KeyModifiers::NONE,
+        }
+    }
+
+    pub fn matches(&self, key: &KeyEvent) -> bool {
+        // shift is part of the character typed, e.g. "N" for shift+n
+        self.code == key.code && self.modifiers == (key.modifiers - KeyModifiers::SHIFT)
+    }
+}
+
+impl TryFrom<String> for KeyBinding {
+    type Error = String;
+
+    fn try_from(binding: String) -> Result<Self, Self::Error> {
+        let lowercase = binding.to_lowercase();
+        let mut parts = lowercase.split('+').map(|part| part.trim()).collect::<Vec<&str>>();
+        let key = parts.pop().unwrap_or_default();
+
+        let mut modifiers = KeyModifiers::NONE;
+        for modifier in parts {
+            modifiers |= match modifier {
+                "ctrl" | "control" => KeyModifiers::CONTROL,
+                "alt" => KeyModifiers::ALT,
+                _ => return Err(format!("unknown modifier '{modifier}' in key '{binding}', try ctrl or alt")),
+            };
+        }
+
+        let code = match key {
+            "esc" | "escape" => KeyCode::Esc,
+            "tab" => KeyCode::Tab,
+            "up" => KeyCode::Up,
+            "down" => KeyCode::Down,
+            "left" => KeyCode::Left,
+            "right" => KeyCode::Right,
+            "home" => KeyCode::Home,
+            "end" => KeyCode::End,
+            "pageup" => KeyCode::PageUp,
+            "pagedown" => KeyCode::PageDown,
+            _ if key.len() > 1 && key.starts_with('f') => match key[1..].parse::<u8>() {
+                Ok(number) if (1..=12).contains(&number) => KeyCode::F(number),
+                _ => return Err(format!("unknown key '{binding}'")),
+            },
+            _ if key.chars().count() == 1 => KeyCode::Char(key.chars().next().unwrap_or_default()),
+            _ => return Err(format!("unknown key '{binding}'")),
+        };
+
+        // plain characters go to the prompt, and Ctrl+C always cancels
+        if let KeyCode::Char(c) = code {
+            if modifiers.is_empty() {
+                return Err(format!("key '{binding}' is needed for typing, add ctrl or alt"));
+            }
+            if c == 'c' && modifiers == KeyModifiers::CONTROL {
+                return Err("ctrl+c is reserved for cancelling lookups and quitting".to_string());
+            }
+        }
+
+        Ok(KeyBinding { code, modifiers })
+    }
+}
+
+/// As the key is written in the config, e.g. "Esc", "F2" or "Ctrl+N".
+impl fmt::Display for KeyBinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "Ctrl+")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "Alt+")?;
+        }
+
+        match self.code {
+            KeyCode::Esc => write!(f, "Esc"),
+            KeyCode::Tab => write!(f, "Tab"),
+            KeyCode::Up => write!(f, "Up"),
+            KeyCode::Down => write!(f, "Down"),
+            KeyCode::Left => write!(f, "Left"),
+            KeyCode::Right => write!(f, "Right"),
+            KeyCode::Home => write!(f, "Home"),
+            KeyCode::End => write!(f, "End"),
+            KeyCode::PageUp => write!(f, "PageUp"),
+            KeyCode::PageDown => write!(f, "PageDown"),
+            KeyCode::F(number) => write!(f, "F{number}"),
+            KeyCode::Char(c) => write!(f, "{}", c.to_uppercase()),
+            code => write!(f, "{code:?}"),
+        }
+    }
+}
+
+#[derive(Deserialize, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct KeyBindings {
+    pub quit: KeyBinding, // also cancels a lookup in progress
+    pub next_table: KeyBinding,
+    pub prev_table: KeyBinding,
+    pub scroll_up: KeyBinding,
+    pub scroll_down: KeyBinding,
+    pub all_tenses: KeyBinding,
+    pub negative: KeyBinding,
+    pub interrogative: KeyBinding,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        Self {
+            quit: KeyBinding::key(KeyCode::Esc),
+            next_table: KeyBinding::key(KeyCode::Right),
+            prev_table: KeyBinding::key(KeyCode::Left),
+            scroll_up: KeyBinding::key(KeyCode::Up),
+            scroll_down: KeyBinding::key(KeyCode::Down),
+            all_tenses: KeyBinding::key(KeyCode::Tab),
+            negative: KeyBinding::key(KeyCode::F(2)),
+            interrogative: KeyBinding::key(KeyCode::F(3)),
+        }
+    }
+}
+
+impl KeyBindings {
+    fn named(&self) -> [(&'static str, KeyBinding); 8] {
+        [
+            ("quit", self.quit),
+            ("next_table", self.next_table),
+            ("prev_table", self.prev_table),
+            ("scroll_up", self.scroll_up),
+            ("scroll_down", self.scroll_down),
+            ("all_tenses", self.all_tenses),
+            ("negative", self.negative),
+            ("interrogative", self.interrogative),
+        ]
+    }
+}
+
+fn language<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+    let language = String::deserialize(deserializer)?;
+    wordreference_utils::parse_language(&language).ok_or_else(|| {
+        D::Error::custom(format!("unsupported language '{language}', try french, italian, spanish or english"))
+    })
+}
+
+fn color<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Color>, D::Error> {
+    let color = String::deserialize(deserializer)?;
+    parse_color(&color)
+        .map(Some)
+        .ok_or_else(|| D::Error::custom(format!("unknown color '{color}'")))
+}
+
+fn theme_name<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<String>, D::Error> {
+    let name = String::deserialize(deserializer)?.to_lowercase();
+    match THEME_NAMES.contains(&name.as_str()) {
+        true => Ok(Some(name)),
+        false => Err(D::Error::custom(format!("unknown theme '{name}', try {}", THEME_NAMES.join(", ")))),
+    }
+}
+
+pub fn parse_color(color: &str) -> Option<Color> {
+    if let Some(hex) = color.strip_prefix('#') {
+        let value = u32::from_str_radix(hex, 16).ok().filter(|_| hex.len() == 6)?;
+        return Some(Color::Rgb((value >> 16) as u8, (value >> 8) as u8, value as u8));
+    }
+
+    let color = match color.to_lowercase().replace(['_', '-', ' '], "").as_str() {
+        "reset" | "default" => Color::Reset,
+        "black" => Color::Black,
+        "red" => Color::Red,
+        "green" => Color::Green,
+        "yellow" => Color::Yellow,
+        "blue" => Color::Blue,
+        "magenta" => Color::Magenta,
+        "cyan" => Color::Cyan,
+        "gray" | "grey" => Color::Gray,
+        "darkgray" | "darkgrey" => Color::DarkGray,
+        "lightred" => Color::LightRed,
+        "lightgreen" => Color::LightGreen,
+        "lightyellow" => Color::LightYellow,
+        "lightblue" => Color::LightBlue,
+        "lightmagenta" => Color::LightMagenta,
+        "lightcyan" => Color::LightCyan,
+        "white" => Color::White,
+        _ => return None,
+    };
+    Some(color)
+}
+
+/// Expands a leading "~/" to the home directory.
+pub fn expand_home(path: &std::path::Path) -> PathBuf {
+    match (path.strip_prefix("~"), home::home_dir()) {
+        (Ok(rest), Some(home)) => home.join(rest),
+        _ => path.to_path_buf(),
+    }
+}
+
+impl Config {
+    /// `$XDG_CONFIG_HOME/transrustate/config.toml`, or under `~/.config`
+    /// when XDG_CONFIG_HOME is unset.
+    pub fn path() -> Option<PathBuf> {
+        let config_home = std::env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .filter(|path| path.is_absolute())
+            .or_else(|| home::home_dir().map(|home| home.join(".config")))?;
+
+        Some(config_home.join("transrustate").join("config.toml"))
+    }
+
+    /// Reads the config file, falling back to the defaults when there is none.
+    pub fn load() -> Result<Config, AppError> {
+        let Some(path) = Config::path().filter(|path| path.exists()) else {
+            return Ok(Config::default());
+        };
+
+        let invalid = |message: String| AppError::Config {
+            message: format!("Invalid config file {}: {message}", path.display()),
+        };
+
+        let contents = fs::read_to_string(&path).map_err(|err| invalid(err.to_string()))?;
+        let config: Config = toml::from_str(&contents).map_err(|err| invalid(err.to_string()))?;
+        config.validate().map_err(invalid)?;
+
+        Ok(config)
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.defaults.language == "english" {
+            return Err("defaults.language can't be english, try french, italian or spanish".to_string());
+        }
+        if self.defaults.language == self.defaults.native_language {
+            return Err("defaults.language and defaults.native_language must differ".to_string());
+        }
+        if self.ui.tick_rate_ms == 0 {
+            return Err("ui.tick_rate_ms must be above 0".to_string());
+        }
+        if self.network.timeout_secs == 0 || self.network.connect_timeout_secs == 0 {
+            return Err("network.timeout_secs and network.connect_timeout_secs must be above 0".to_string());
+        }
+
+        let bindings = self.keys.named();
+        for (index, (name, binding)) in bindings.iter().enumerate() {
+            if let Some((other, _)) = bindings[index + 1..].iter().find(|(_, other)| other == binding) {
+                return Err(format!("keys.{name} and keys.{other} are bound to the same key"));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn binding(binding: &str) -> Result<KeyBinding, String> {
+        KeyBinding::try_from(binding.to_string())
+    }
+
+    fn parse(contents: &str) -> Result<Config, String> {
+        let config: Config = toml::from_str(contents).map_err(|err| err.to_string())?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    #[test]
+    fn keys_parse_with_modifiers() {
+        assert_eq!(binding("F2"), Ok(KeyBinding::key(KeyCode::F(2))));
+        assert_eq!(binding("esc"), Ok(KeyBinding::key(KeyCode::Esc)));
+        assert_eq!(
+            binding("Ctrl + Alt + n"),
+            Ok(KeyBinding {
+                code: KeyCode::Char('n'),
+                modifiers: KeyModifiers::CONTROL | KeyModifiers::ALT,
+            }),
+        );
+    }
+
+    #[test]
+    fn keys_display_as_written() {
+        assert_eq!(KeyBinding::key(KeyCode::Esc).to_string(), "Esc");
+        assert_eq!(binding("f2").map(|key| key.to_string()), Ok("F2".to_string()));
+        assert_eq!(binding("alt+ctrl+n").map(|key| key.to_string()), Ok("Ctrl+Alt+N".to_string()));
+    }
+
+    #[test]
+    fn keys_needed_for_typing_or_cancelling_are_rejected() {
+        assert!(binding("n").is_err());
+        assert!(binding("ctrl+c").is_err());
+        assert!(binding("shift+n").is_err());
+        assert!(binding("f13").is_err());
+        assert!(binding("space bar").is_err());
+    }
+
+    #[test]
+    fn an_empty_config_has_the_defaults() {
+        let config = parse("").expect("a valid config");
+        assert_eq!(config.defaults.language, "french");
+        assert_eq!(config.keys.quit, KeyBinding::key(KeyCode::Esc));
+    }
+
+    #[test]
+    fn keys_bound_twice_are_rejected() {
+        let err = parse("[keys]\nnegative = \"tab\"\n").err().expect("a duplicate binding");
+        assert_eq!(err, "keys.all_tenses and keys.negative are bound to the same key");
+        assert!(parse("[keys]\nnegative = \"tab\"\nall_tenses = \"f4\"\n").is_ok());
+    }
+
+    #[test]
+    fn the_language_must_differ_from_the_native_language() {
+        assert!(parse("[defaults]\nlanguage = \"es\"\nnative_language = \"spanish\"\n").is_err());
+        assert!(parse("[defaults]\nlanguage = \"english\"\n").is_err());
+        assert!(parse("[defaults]\nlanguage = \"german\"\n").is_err());
+
+        let config = parse("[defaults]\nlanguage = \"it\"\n").expect("a valid config");
+        assert_eq!(config.defaults.language, "italian");
+    }
+
+    #[test]
+    fn unknown_settings_are_rejected() {
+        assert!(parse("colour = \"red\"\n").is_err());
+        assert!(parse("[theme]\nforeground = \"red\"\nborder = \"blue\"\n").is_err());
+        assert!(parse("[theme]\nforeground = \"reddish\"\n").is_err());
+    }
+}
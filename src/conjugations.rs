@@ -1,67 +1,176 @@
-use reqwest::{self, Client};
 use scraper::{Html, ElementRef};
 
-use crate::{wordreference::wordreference_utils, user_error::UserError};
+use crate::{
+    app_error::AppError,
+    http::{FetchError, HttpClient},
+    status::{LoadStage, Progress},
+    wordreference::wordreference_utils,
+    grammar::{self, Mood, Tense, Person},
+};
 use serde::{Serialize, Deserialize};
 
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ConjugatedForm {
+    pub person: Option<Person>,
+    pub pronoun: String,
+    pub form: String,
+    pub auxiliary: Option<String>, // "ai" in "ai parlé"
+    pub participle: Option<String>, // "parlé" in "ai parlé"
+}
+
+impl ConjugatedForm {
+    fn new(pronoun: &str, form: &str, person: Option<Person>, tense: Tense) -> ConjugatedForm {
+        let form = form.trim().to_string();
+
+        // compound tenses are an auxiliary followed by a participle
+        let (auxiliary, participle) = match form.rsplit_once(' ') {
+            Some((auxiliary, participle)) if tense.is_compound() => (
+                Some(auxiliary.trim().to_string()),
+                Some(participle.trim().to_string()),
+            ),
+            _ => (None, None),
+        };
+
+        ConjugatedForm {
+            person,
+            pronoun: pronoun.trim().to_string(),
+            form,
+            auxiliary,
+            participle,
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone)]
 pub struct ConjugationTable {
+    #[serde(default)]
+    pub mood_label: String,
     pub tense: String,
-    pub conjugations: Vec<Vec<String>>, // [[1, 2], [3, 4]]
+    #[serde(default)]
+    pub mood: Mood,
+    #[serde(default)]
+    pub tense_kind: Tense,
+    #[serde(default)]
+    pub forms: Vec<ConjugatedForm>,
+
+    // pronoun/conjugation pairs from caches written before forms were typed
+    #[serde(default, skip_serializing)]
+    conjugations: Vec<Vec<String>>, // [[1, 2], [3, 4]]
 }
 
 impl ConjugationTable {
-    fn new(cell_values: Vec<String>) -> ConjugationTable {
+    fn new(mood_label: &str, cell_values: Vec<String>) -> ConjugationTable {
         // skip header text
         let mut text_iter = cell_values.iter().skip(1);
-        
+
         // get pronoun/conjugation pairs remaining
-        let mut conjugations: Vec<Vec<String>> = Vec::new();
-        loop {
-            if let Some(pronoun) = text_iter.next() {
-                if let Some(conjugation) = text_iter.next() {
-                    conjugations.push(vec![pronoun.to_string(), conjugation.to_string()]);
-                } else {
-                    break;
-                }
+        let mut pairs: Vec<(String, String)> = Vec::new();
+        while let Some(pronoun) = text_iter.next() {
+            if let Some(conjugation) = text_iter.next() {
+                pairs.push((pronoun.to_string(), conjugation.to_string()));
             } else {
                 break;
             }
         }
 
-        let tense = cell_values[0].to_string();
+        let tense = cell_values[0].trim().to_string();
+
+        ConjugationTable::from_pairs(mood_label, &tense, pairs)
+    }
+
+    pub fn from_pairs(mood_label: &str, tense: &str, pairs: Vec<(String, String)>) -> ConjugationTable {
+        let tense_kind = Tense::parse(tense);
+
+        // the tense label can name the mood itself, e.g. "condicional"
+        let mood = match Mood::parse(tense) {
+            Mood::Unknown => match Mood::parse(mood_label) {
+                Mood::Unknown => Mood::Indicative,
+                mood => mood,
+            },
+            mood => mood,
+        };
+
+        let num_rows = pairs.len();
+        let forms = pairs
+            .iter()
+            .enumerate()
+            .map(|(index, (pronoun, form))| {
+                let person = Person::parse(pronoun)
+                    .or_else(|| Person::from_position(index, num_rows));
+                ConjugatedForm::new(pronoun, form, person, tense_kind)
+            })
+            .collect();
 
         ConjugationTable {
-            tense: tense,
-            conjugations: conjugations,
+            mood_label: mood_label.trim().to_string(),
+            tense: tense.to_string(),
+            mood,
+            tense_kind,
+            forms,
+            conjugations: Vec::new(),
         }
     }
 
-    pub fn conjugations_as_strings(&self) -> Vec<Vec<String>> {
-        let mut conj_table: Vec<Vec<String>> = Vec::new();
-        for vector in &self.conjugations {
-            let mut conj_row: Vec<String> = Vec::new();
-            for string in vector {
-                conj_row.push(string.to_string());
-            }
-            conj_table.push(conj_row);
+    /// Rebuilds the typed forms of a table cached before they were stored.
+    fn upgrade_legacy(&mut self) {
+        if !self.forms.is_empty() || self.conjugations.is_empty() {
+            return;
+        }
+
+        let pairs = self.conjugations
+            .drain(..)
+            .filter(|pair| pair.len() == 2)
+            .map(|pair| (pair[0].clone(), pair[1].clone()))
+            .collect();
+
+        *self = ConjugationTable::from_pairs(&self.mood_label, &self.tense, pairs);
+    }
+
+    /// The raw mood and tense labels as scraped, e.g. "indicatif présent".
+    pub fn label(&self) -> String {
+        match self.mood_label.is_empty() {
+            true => self.tense.clone(),
+            false => format!("{} {}", self.mood_label, self.tense),
         }
+    }
 
-        conj_table
+    pub fn conjugations_as_strings(&self) -> Vec<Vec<String>> {
+        self.forms
+            .iter()
+            .map(|form| vec![form.pronoun.clone(), form.form.clone()])
+            .collect()
     }
 }
 
 #[derive(Serialize, Deserialize, Clone)]
 pub struct VerbConjugations {
     pub verb: String,
+    #[serde(default)]
+    pub language: String,
     pub conjugation_tables: Vec<ConjugationTable>,
+    // built offline by the generator rather than scraped
+    #[serde(default)]
+    pub generated: bool,
+
+    // what compound tenses are built from, e.g. "être" and "venu" for "venir"
+    #[serde(default)]
+    pub auxiliary: Option<String>,
+    #[serde(default)]
+    pub past_participle: Option<String>,
+    #[serde(default)]
+    pub present_participle: Option<String>,
 }
 
 impl VerbConjugations {
     fn new() -> VerbConjugations {
         VerbConjugations {
             verb: "".to_string(),
+            language: "".to_string(),
             conjugation_tables: Vec::new(),
+            generated: false,
+            auxiliary: None,
+            past_participle: None,
+            present_participle: None,
         }
     }
 
@@ -69,42 +178,179 @@ impl VerbConjugations {
         VerbConjugations::new()
     }
 
+    /// Deserializes a cached entry, typing the tables of older entries.
+    pub fn from_cache(json: &str, language: &str) -> serde_json::Result<VerbConjugations> {
+        let mut conjugations: VerbConjugations = serde_json::from_str(json)?;
+        conjugations.language = language.to_string();
+        for table in conjugations.conjugation_tables.iter_mut() {
+            table.upgrade_legacy();
+        }
+        conjugations.infer_verb_info();
+
+        Ok(conjugations)
+    }
+
+    /// Finds the table whose mood and tense labels best match a query such
+    /// as "subj imparfait", or None if some word of the query matches nothing.
+    pub fn find_table(&self, query: &str, language: &str) -> Option<usize> {
+        let query_words = grammar::expand_tense_query(language, query);
+        if query_words.is_empty() {
+            return None;
+        }
+
+        let mut best: Option<(usize, i64)> = None;
+        for (index, table) in self.conjugation_tables.iter().enumerate() {
+            let label = grammar::fold(&table.label());
+            let label_words = label.split_whitespace().collect::<Vec<&str>>();
+
+            let mut score: i64 = 0;
+            let mut matched_words = 0;
+            for query_word in &query_words {
+                let word_score = label_words
+                    .iter()
+                    .filter_map(|label_word| grammar::word_match_score(query_word, label_word))
+                    .max();
+
+                match word_score {
+                    Some(word_score) => {
+                        score += 10 * word_score as i64;
+                        matched_words += 1;
+                    }
+                    None => break,
+                }
+            }
+
+            if matched_words < query_words.len() {
+                continue;
+            }
+
+            // prefer "pretérito" over "pretérito perfecto" for "pret"
+            score -= label_words.len() as i64;
+
+            if best.map_or(true, |(_, best_score)| score > best_score) {
+                best = Some((index, score));
+            }
+        }
+
+        best.map(|(index, _)| index)
+    }
+
+    /// Fills in what the page summary did not give from the first person
+    /// of the perfect, e.g. "je suis venu(e)" takes "être" and has "venu".
+    fn infer_verb_info(&mut self) {
+        let perfect_form = self.conjugation_tables
+            .iter()
+            .find(|table| table.mood == Mood::Indicative && table.tense_kind == Tense::Perfect)
+            .and_then(|table| table.forms.iter().find(|form| form.person == Some(Person::FirstSingular)));
+
+        let Some(perfect_form) = perfect_form else {
+            return;
+        };
+
+        if self.auxiliary.is_none() {
+            // the last word skips reflexive pronouns, as in "me suis"
+            let auxiliary = perfect_form.auxiliary
+                .as_deref()
+                .and_then(|auxiliary| auxiliary.split_whitespace().last())
+                .map(grammar::fold);
+
+            self.auxiliary = match auxiliary.as_deref() {
+                Some("ai") => Some("avoir"),
+                Some("suis") => Some("être"),
+                Some("he") => Some("haber"),
+                Some("ho") => Some("avere"),
+                Some("sono") => Some("essere"),
+                _ => None,
+            }
+            .map(|auxiliary| auxiliary.to_string());
+        }
+
+        if self.past_participle.is_none() {
+            // drop agreement markers like "venu(e)" and "andato/a"
+            self.past_participle = perfect_form.participle
+                .as_deref()
+                .and_then(|participle| participle.split(['(', '/']).next())
+                .map(|participle| participle.trim().to_string())
+                .filter(|participle| !participle.is_empty());
+        }
+    }
+
+    /// Reads the summary above the tables, rows such as "participe passé: venu".
+    fn extract_verb_info(&mut self, document: &Html) {
+        let row_query = scraper::Selector::parse("#conjtable tr")
+            .expect("verb summary should have rows");
+        let cell_query = scraper::Selector::parse("td")
+            .expect("verb summary rows should have cells");
+
+        for row in document.select(&row_query) {
+            let cells = row
+                .select(&cell_query)
+                .map(|cell| cell.text().collect::<String>().trim().to_string())
+                .collect::<Vec<String>>();
+
+            // the label either has its own cell or precedes a colon
+            let (label, value) = match cells.as_slice() {
+                [label, value, ..] => (grammar::fold(label), value.clone()),
+                [cell] => match cell.split_once(':') {
+                    Some((label, value)) => (grammar::fold(label), value.trim().to_string()),
+                    None => continue,
+                },
+                _ => continue,
+            };
+
+            if value.is_empty() {
+                continue;
+            }
+
+            if label.contains("auxil") {
+                self.auxiliary = Some(value);
+            } else if label.contains("gerundi") || (label.contains("partic") && label.contains("present")) {
+                self.present_participle = Some(value);
+            } else if label.contains("partic") {
+                self.past_participle = Some(value);
+            }
+        }
+    }
+
+    /// Groups tables under their mood label, keeping the scraped order.
+    pub fn tables_by_mood(&self) -> Vec<(String, Vec<&ConjugationTable>)> {
+        let mut moods: Vec<(String, Vec<&ConjugationTable>)> = Vec::new();
+        for table in &self.conjugation_tables {
+            match moods.iter_mut().find(|(mood_label, _)| *mood_label == table.mood_label) {
+                Some((_, tables)) => tables.push(table),
+                None => moods.push((table.mood_label.clone(), vec![table])),
+            }
+        }
+
+        moods
+    }
+
     async fn scrape_conjugation_tables(
         &mut self,
         verb: &str,
         language: &str,
-        client: &Client,
-    ) -> Result<Vec<Html>, UserError> {
-        let not_exist_error = UserError {
+        client: &HttpClient,
+        progress: &Progress,
+    ) -> Result<Vec<(String, Html)>, AppError> {
+        let not_exist_error = AppError::NotFound {
             message: format!(
                 "The verb '{verb}' does not exist in the selected language \
                 ({language}). Please double check your spelling",
             )
         };
 
-        let network_error = UserError {
-            message: "Could not find the corresponding verb conjugaitons, \
-            please check your network connection and spelling".to_string(),
-        };
-
         let verb_query_url = wordreference_utils::conjugation_url(
+            client.base_url(),
             language.to_string(),
             verb.to_string(),
         );
 
-        let response = match match client.get(
-                verb_query_url,
-            )
-            .send()
-            .await {
-                Ok(it) => it,
-                Err(_err) => return Err(network_error),
-            }
-            .text()
-            .await {
-                Ok(it) => it,
-                Err(_err) => return Err(not_exist_error),
-            };
+        let response = match client.get_text(&verb_query_url, progress).await {
+            Ok(response) => response,
+            Err(FetchError::Status { status: 404, .. }) => return Err(not_exist_error),
+            Err(err) => return Err(err.into()),
+        };
+        progress(LoadStage::Parsing);
 
         let document = scraper::Html::parse_document(&response);
 
@@ -115,7 +361,7 @@ impl VerbConjugations {
             .select(&infinitive_query)
             .collect::<Vec<ElementRef>>();
 
-        if infinitive_cells.len() == 0 {
+        if infinitive_cells.is_empty() {
             return Err(not_exist_error);
         }
 
@@ -129,14 +375,24 @@ impl VerbConjugations {
             .to_string();
 
         self.verb = infinitive.clone();
+        self.extract_verb_info(&document);
 
-        let table_query = scraper::Selector::parse("table.neoConj")
+        // mood headings precede the tables of their tenses in document order
+        let table_query = scraper::Selector::parse("h4, table.neoConj")
             .expect("verb conjugation should have a table with the neoConj class");
-        let tables = document
-            .select(&table_query)
-            .map(|x| scraper::Html::parse_fragment(&x.html()));
 
-        let tables = tables.collect::<Vec<Html>>();
+        let mut mood_label = String::new();
+        let mut tables: Vec<(String, Html)> = Vec::new();
+        for element in document.select(&table_query) {
+            if element.value().name() == "h4" {
+                mood_label = element.text().collect::<String>();
+            } else {
+                tables.push((
+                    mood_label.clone(),
+                    scraper::Html::parse_fragment(&element.html()),
+                ));
+            }
+        }
 
         match tables.len() {
             0 => {
@@ -148,7 +404,7 @@ impl VerbConjugations {
         }
     }
 
-    fn extract_conjugations_from_table(&mut self, table: Html) {        
+    fn extract_conjugations_from_table(&mut self, mood_label: &str, table: Html) {
         let row_query = scraper::Selector::parse("tr")
             .expect("conjugation table should have rows");
         let cell_query = scraper::Selector::parse("td, th")
@@ -158,38 +414,132 @@ impl VerbConjugations {
 
         // concatenate all text inside a cell first
         // this is because there can be further nested elements
-        let cell_values = rows.map(|row| {
+        let cell_values = rows.flat_map(|row| {
             let cells = row.select(&cell_query);
             cells.map(|cell| {
                 cell.text().collect::<String>()
             })
                 .collect::<Vec<String>>()
         })
-            .flatten()
             .collect::<Vec<String>>();
 
-        self.conjugation_tables.push(ConjugationTable::new(cell_values));
+        if !cell_values.is_empty() {
+            self.conjugation_tables.push(ConjugationTable::new(mood_label, cell_values));
+        }
     }
 
     pub async fn get_conjugation_tables(
         verb: &str,
         language: &str,
-        client: &Client,
-    ) -> Result<VerbConjugations, UserError> {
+        client: &HttpClient,
+        progress: &Progress,
+    ) -> Result<VerbConjugations, AppError> {
         let mut verb_conjugations = VerbConjugations::new();
+        verb_conjugations.language = language.to_string();
         let tables_result = verb_conjugations
-            .scrape_conjugation_tables(verb, language, client).await;
+            .scrape_conjugation_tables(verb, language, client, progress).await;
         match tables_result {
             Err(err) => {
                 Err(err)
             }
             Ok(tables) => {
-                for table in tables {
-                    verb_conjugations.extract_conjugations_from_table(table);
+                for (mood_label, table) in tables {
+                    verb_conjugations.extract_conjugations_from_table(&mood_label, table);
                 }
 
+                // the summary's markup isn't guaranteed, so whatever it didn't
+                // give, or all of it when no row matched, comes from the tables
+                verb_conjugations.infer_verb_info();
+
                 Ok(verb_conjugations)
             }
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn french_tables() -> VerbConjugations {
+        let mut conjugations = VerbConjugations::new();
+        for (mood, tense) in [
+            ("indicatif", "présent"),
+            ("indicatif", "imparfait"),
+            ("indicatif", "passé simple"),
+            ("indicatif", "passé composé"),
+            ("subjonctif", "présent"),
+            ("subjonctif", "imparfait"),
+        ] {
+            conjugations.conjugation_tables.push(ConjugationTable::from_pairs(mood, tense, Vec::new()));
+        }
+        conjugations
+    }
+
+    #[test]
+    fn tense_queries_match_with_or_without_accents() {
+        let conjugations = french_tables();
+        assert_eq!(conjugations.find_table("passé composé", "french"), Some(3));
+        assert_eq!(conjugations.find_table("passe compose", "french"), Some(3));
+        assert_eq!(conjugations.find_table("Subjonctif Présent", "french"), Some(4));
+    }
+
+    #[test]
+    fn abbreviated_queries_find_their_table() {
+        let conjugations = french_tables();
+        assert_eq!(conjugations.find_table("pc", "french"), Some(3));
+        assert_eq!(conjugations.find_table("ps", "french"), Some(2));
+        assert_eq!(conjugations.find_table("subj imp", "french"), Some(5));
+        assert_eq!(conjugations.find_table("subj", "french"), Some(4));
+    }
+
+    #[test]
+    fn typos_still_match_but_unknown_words_do_not() {
+        let conjugations = french_tables();
+        assert_eq!(conjugations.find_table("imparfiat", "french"), Some(1));
+        assert_eq!(conjugations.find_table("gerondif", "french"), None);
+        assert_eq!(conjugations.find_table("", "french"), None);
+    }
+
+    fn venir_tables() -> VerbConjugations {
+        let mut conjugations = VerbConjugations::new();
+        conjugations.conjugation_tables.push(ConjugationTable::from_pairs(
+            "indicatif",
+            "passé composé",
+            vec![("je".to_string(), "suis venu(e)".to_string())],
+        ));
+        conjugations
+    }
+
+    #[test]
+    fn verb_info_is_read_from_the_page_summary() {
+        let document = Html::parse_document(
+            "<table id='conjtable'>\
+                <tr><td>infinitif</td><td>venir</td></tr>\
+                <tr><td>participe présent:</td><td>venant</td></tr>\
+                <tr><td>participe passé: venu</td></tr>\
+                <tr><td>auxiliaire</td><td>être</td></tr>\
+            </table>",
+        );
+
+        let mut conjugations = VerbConjugations::new();
+        conjugations.extract_verb_info(&document);
+        assert_eq!(conjugations.present_participle.as_deref(), Some("venant"));
+        assert_eq!(conjugations.past_participle.as_deref(), Some("venu"));
+        assert_eq!(conjugations.auxiliary.as_deref(), Some("être"));
+    }
+
+    #[test]
+    fn verb_info_falls_back_to_the_perfect_without_a_summary() {
+        let document = Html::parse_document("<div id='conjtable'><p>venir</p></div>");
+
+        let mut conjugations = venir_tables();
+        conjugations.extract_verb_info(&document);
+        assert_eq!(conjugations.auxiliary, None);
+
+        conjugations.infer_verb_info();
+        assert_eq!(conjugations.auxiliary.as_deref(), Some("être"));
+        assert_eq!(conjugations.past_participle.as_deref(), Some("venu"));
+        assert_eq!(conjugations.present_participle, None);
+    }
+}
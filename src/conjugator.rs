@@ -0,0 +1,393 @@
+use crate::{
+    conjugations::{ConjugatedForm, ConjugationTable},
+    grammar::{Mood, Person, Tense},
+};
+
+/// Endings for the six persons, in `Person::ALL` order. "-" marks a person
+/// the tense has no form for, like the first person imperative.
+type Endings = [&'static str; 6];
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum VerbClass {
+    FrenchEr,
+    FrenchIr,
+    FrenchRe,
+    SpanishAr,
+    SpanishEr,
+    SpanishIr,
+    ItalianAre,
+    ItalianEre,
+    ItalianIre,
+}
+
+impl VerbClass {
+    fn from_infinitive(language: &str, infinitive: &str) -> Option<VerbClass> {
+        let class = match language {
+            "french" if infinitive.ends_with("er") => VerbClass::FrenchEr,
+            "french" if infinitive.ends_with("ir") => VerbClass::FrenchIr,
+            "french" if infinitive.ends_with("re") => VerbClass::FrenchRe,
+            "spanish" if infinitive.ends_with("ar") => VerbClass::SpanishAr,
+            "spanish" if infinitive.ends_with("er") => VerbClass::SpanishEr,
+            "spanish" if infinitive.ends_with("ir") => VerbClass::SpanishIr,
+            "italian" if infinitive.ends_with("are") => VerbClass::ItalianAre,
+            "italian" if infinitive.ends_with("ere") => VerbClass::ItalianEre,
+            "italian" if infinitive.ends_with("ire") => VerbClass::ItalianIre,
+            _ => return None,
+        };
+
+        Some(class)
+    }
+
+    fn ending_len(&self) -> usize {
+        match self {
+            VerbClass::ItalianAre | VerbClass::ItalianEre | VerbClass::ItalianIre => 3,
+            _ => 2,
+        }
+    }
+}
+
+/// Conjugates a verb as if it followed the regular pattern of its class,
+/// e.g. any french verb in -er like "parler".
+pub struct RegularConjugator {
+    pub class: VerbClass,
+    pub infinitive: String,
+    pub stem: String,
+}
+
+impl RegularConjugator {
+    pub fn new(language: &str, infinitive: &str) -> Option<RegularConjugator> {
+        let infinitive = base_infinitive(language, infinitive);
+        let class = VerbClass::from_infinitive(language, &infinitive)?;
+        let stem = infinitive[..infinitive.len() - class.ending_len()].to_string();
+        if stem.is_empty() {
+            return None;
+        }
+
+        Some(RegularConjugator {
+            class,
+            infinitive,
+            stem,
+        })
+    }
+
+    pub fn past_participle(&self) -> String {
+        let ending = match self.class {
+            VerbClass::FrenchEr => "é",
+            VerbClass::FrenchIr => "i",
+            VerbClass::FrenchRe => "u",
+            VerbClass::SpanishAr => "ado",
+            VerbClass::SpanishEr | VerbClass::SpanishIr => "ido",
+            VerbClass::ItalianAre => "ato",
+            VerbClass::ItalianEre => "uto",
+            VerbClass::ItalianIre => "ito",
+        };
+
+        format!("{}{ending}", self.stem)
+    }
+
+    /// All regular forms for a mood, tense and person. Some cells accept
+    /// several forms, e.g. spanish "hablara" and "hablase". Compound tenses
+    /// return the participle since only that part can be irregular.
+    pub fn forms(&self, mood: Mood, tense: Tense, person: Person) -> Vec<String> {
+        if tense.is_compound() {
+            return vec![self.past_participle()];
+        }
+
+        let index = Person::ALL
+            .iter()
+            .position(|x| *x == person)
+            .expect("person is one of the six persons");
+
+        self.endings(mood, tense)
+            .into_iter()
+            .map(|(base, endings)| self.join(&base, endings[index]))
+            .filter(|form| !form.is_empty())
+            .collect()
+    }
+
+    /// The stems and endings that build each tense, or nothing when the tense
+    /// has no rule for this class.
+    fn endings(&self, mood: Mood, tense: Tense) -> Vec<(String, Endings)> {
+        let stem = self.stem.clone();
+        let infinitive = self.infinitive.clone();
+
+        match (self.class, mood, tense) {
+            (VerbClass::FrenchEr, Mood::Indicative, Tense::Present) => vec![(stem, ["e", "es", "e", "ons", "ez", "ent"])],
+            (VerbClass::FrenchIr, Mood::Indicative, Tense::Present) => vec![(stem, ["is", "is", "it", "issons", "issez", "issent"])],
+            (VerbClass::FrenchRe, Mood::Indicative, Tense::Present) => vec![(stem, ["s", "s", "", "ons", "ez", "ent"])],
+            (VerbClass::FrenchIr, Mood::Indicative, Tense::Imperfect) => vec![(stem, ["issais", "issais", "issait", "issions", "issiez", "issaient"])],
+            (VerbClass::FrenchEr | VerbClass::FrenchRe, Mood::Indicative, Tense::Imperfect) => vec![(stem, ["ais", "ais", "ait", "ions", "iez", "aient"])],
+            (VerbClass::FrenchEr, Mood::Indicative, Tense::Preterite) => vec![(stem, ["ai", "as", "a", "âmes", "âtes", "èrent"])],
+            (VerbClass::FrenchIr | VerbClass::FrenchRe, Mood::Indicative, Tense::Preterite) => vec![(stem, ["is", "is", "it", "îmes", "îtes", "irent"])],
+            (VerbClass::FrenchEr | VerbClass::FrenchIr, Mood::Indicative, Tense::Future) => vec![(infinitive, ["ai", "as", "a", "ons", "ez", "ont"])],
+            (VerbClass::FrenchRe, Mood::Indicative, Tense::Future) => vec![(format!("{stem}r"), ["ai", "as", "a", "ons", "ez", "ont"])],
+            (VerbClass::FrenchEr | VerbClass::FrenchIr, Mood::Conditional, Tense::Present) => vec![(infinitive, ["ais", "ais", "ait", "ions", "iez", "aient"])],
+            (VerbClass::FrenchRe, Mood::Conditional, Tense::Present) => vec![(format!("{stem}r"), ["ais", "ais", "ait", "ions", "iez", "aient"])],
+            (VerbClass::FrenchEr | VerbClass::FrenchRe, Mood::Subjunctive, Tense::Present) => vec![(stem, ["e", "es", "e", "ions", "iez", "ent"])],
+            (VerbClass::FrenchIr, Mood::Subjunctive, Tense::Present) => vec![(stem, ["isse", "isses", "isse", "issions", "issiez", "issent"])],
+            (VerbClass::FrenchEr, Mood::Subjunctive, Tense::Imperfect) => vec![(stem, ["asse", "asses", "ât", "assions", "assiez", "assent"])],
+            (VerbClass::FrenchIr | VerbClass::FrenchRe, Mood::Subjunctive, Tense::Imperfect) => vec![(stem, ["isse", "isses", "ît", "issions", "issiez", "issent"])],
+            (VerbClass::FrenchEr, Mood::Imperative, _) => vec![(stem, ["-", "e", "-", "ons", "ez", "-"])],
+            (VerbClass::FrenchIr, Mood::Imperative, _) => vec![(stem, ["-", "is", "-", "issons", "issez", "-"])],
+            (VerbClass::FrenchRe, Mood::Imperative, _) => vec![(stem, ["-", "s", "-", "ons", "ez", "-"])],
+
+            (VerbClass::SpanishAr, Mood::Indicative, Tense::Present) => vec![(stem, ["o", "as", "a", "amos", "áis", "an"])],
+            (VerbClass::SpanishEr, Mood::Indicative, Tense::Present) => vec![(stem, ["o", "es", "e", "emos", "éis", "en"])],
+            (VerbClass::SpanishIr, Mood::Indicative, Tense::Present) => vec![(stem, ["o", "es", "e", "imos", "ís", "en"])],
+            (VerbClass::SpanishAr, Mood::Indicative, Tense::Imperfect) => vec![(stem, ["aba", "abas", "aba", "ábamos", "abais", "aban"])],
+            (VerbClass::SpanishEr | VerbClass::SpanishIr, Mood::Indicative, Tense::Imperfect) => vec![(stem, ["ía", "ías", "ía", "íamos", "íais", "ían"])],
+            (VerbClass::SpanishAr, Mood::Indicative, Tense::Preterite) => vec![(stem, ["é", "aste", "ó", "amos", "asteis", "aron"])],
+            (VerbClass::SpanishEr | VerbClass::SpanishIr, Mood::Indicative, Tense::Preterite) => vec![(stem, ["í", "iste", "ió", "imos", "isteis", "ieron"])],
+            (VerbClass::SpanishAr | VerbClass::SpanishEr | VerbClass::SpanishIr, Mood::Indicative, Tense::Future) => vec![(infinitive, ["é", "ás", "á", "emos", "éis", "án"])],
+            (VerbClass::SpanishAr | VerbClass::SpanishEr | VerbClass::SpanishIr, Mood::Conditional, Tense::Present) => vec![(infinitive, ["ía", "ías", "ía", "íamos", "íais", "ían"])],
+            (VerbClass::SpanishAr, Mood::Subjunctive, Tense::Present) => vec![(stem, ["e", "es", "e", "emos", "éis", "en"])],
+            (VerbClass::SpanishEr | VerbClass::SpanishIr, Mood::Subjunctive, Tense::Present) => vec![(stem, ["a", "as", "a", "amos", "áis", "an"])],
+            (VerbClass::SpanishAr, Mood::Subjunctive, Tense::Imperfect) => vec![
+                (stem.clone(), ["ara", "aras", "ara", "áramos", "arais", "aran"]),
+                (stem, ["ase", "ases", "ase", "ásemos", "aseis", "asen"]),
+            ],
+            (VerbClass::SpanishEr | VerbClass::SpanishIr, Mood::Subjunctive, Tense::Imperfect) => vec![
+                (stem.clone(), ["iera", "ieras", "iera", "iéramos", "ierais", "ieran"]),
+                (stem, ["iese", "ieses", "iese", "iésemos", "ieseis", "iesen"]),
+            ],
+            (VerbClass::SpanishAr, Mood::Subjunctive, Tense::Future) => vec![(stem, ["are", "ares", "are", "áremos", "areis", "aren"])],
+            (VerbClass::SpanishEr | VerbClass::SpanishIr, Mood::Subjunctive, Tense::Future) => vec![(stem, ["iere", "ieres", "iere", "iéremos", "iereis", "ieren"])],
+            (VerbClass::SpanishAr, Mood::Imperative, _) => vec![(stem, ["-", "a", "e", "emos", "ad", "en"])],
+            (VerbClass::SpanishEr, Mood::Imperative, _) => vec![(stem, ["-", "e", "a", "amos", "ed", "an"])],
+            (VerbClass::SpanishIr, Mood::Imperative, _) => vec![(stem, ["-", "e", "a", "amos", "id", "an"])],
+
+            (VerbClass::ItalianAre, Mood::Indicative, Tense::Present) => vec![(stem, ["o", "i", "a", "iamo", "ate", "ano"])],
+            (VerbClass::ItalianEre, Mood::Indicative, Tense::Present) => vec![(stem, ["o", "i", "e", "iamo", "ete", "ono"])],
+            (VerbClass::ItalianIre, Mood::Indicative, Tense::Present) => vec![
+                (stem.clone(), ["o", "i", "e", "iamo", "ite", "ono"]),
+                (stem, ["isco", "isci", "isce", "iamo", "ite", "iscono"]),
+            ],
+            (VerbClass::ItalianAre, Mood::Indicative, Tense::Imperfect) => vec![(stem, ["avo", "avi", "ava", "avamo", "avate", "avano"])],
+            (VerbClass::ItalianEre, Mood::Indicative, Tense::Imperfect) => vec![(stem, ["evo", "evi", "eva", "evamo", "evate", "evano"])],
+            (VerbClass::ItalianIre, Mood::Indicative, Tense::Imperfect) => vec![(stem, ["ivo", "ivi", "iva", "ivamo", "ivate", "ivano"])],
+            (VerbClass::ItalianAre, Mood::Indicative, Tense::Preterite) => vec![(stem, ["ai", "asti", "ò", "ammo", "aste", "arono"])],
+            (VerbClass::ItalianEre, Mood::Indicative, Tense::Preterite) => vec![
+                (stem.clone(), ["ei", "esti", "é", "emmo", "este", "erono"]),
+                (stem, ["etti", "esti", "ette", "emmo", "este", "ettero"]),
+            ],
+            (VerbClass::ItalianIre, Mood::Indicative, Tense::Preterite) => vec![(stem, ["ii", "isti", "ì", "immo", "iste", "irono"])],
+            (VerbClass::ItalianAre | VerbClass::ItalianEre, Mood::Indicative, Tense::Future) => vec![(stem, ["erò", "erai", "erà", "eremo", "erete", "eranno"])],
+            (VerbClass::ItalianIre, Mood::Indicative, Tense::Future) => vec![(stem, ["irò", "irai", "irà", "iremo", "irete", "iranno"])],
+            (VerbClass::ItalianAre | VerbClass::ItalianEre, Mood::Conditional, Tense::Present) => vec![(stem, ["erei", "eresti", "erebbe", "eremmo", "ereste", "erebbero"])],
+            (VerbClass::ItalianIre, Mood::Conditional, Tense::Present) => vec![(stem, ["irei", "iresti", "irebbe", "iremmo", "ireste", "irebbero"])],
+            (VerbClass::ItalianAre, Mood::Subjunctive, Tense::Present) => vec![(stem, ["i", "i", "i", "iamo", "iate", "ino"])],
+            (VerbClass::ItalianEre, Mood::Subjunctive, Tense::Present) => vec![(stem, ["a", "a", "a", "iamo", "iate", "ano"])],
+            (VerbClass::ItalianIre, Mood::Subjunctive, Tense::Present) => vec![
+                (stem.clone(), ["a", "a", "a", "iamo", "iate", "ano"]),
+                (stem, ["isca", "isca", "isca", "iamo", "iate", "iscano"]),
+            ],
+            (VerbClass::ItalianAre, Mood::Subjunctive, Tense::Imperfect) => vec![(stem, ["assi", "assi", "asse", "assimo", "aste", "assero"])],
+            (VerbClass::ItalianEre, Mood::Subjunctive, Tense::Imperfect) => vec![(stem, ["essi", "essi", "esse", "essimo", "este", "essero"])],
+            (VerbClass::ItalianIre, Mood::Subjunctive, Tense::Imperfect) => vec![(stem, ["issi", "issi", "isse", "issimo", "iste", "issero"])],
+            (VerbClass::ItalianAre, Mood::Imperative, _) => vec![(stem, ["-", "a", "i", "iamo", "ate", "ino"])],
+            (VerbClass::ItalianEre, Mood::Imperative, _) => vec![(stem, ["-", "i", "a", "iamo", "ete", "ano"])],
+            (VerbClass::ItalianIre, Mood::Imperative, _) => vec![
+                (stem.clone(), ["-", "i", "a", "iamo", "ite", "ano"]),
+                (stem, ["-", "isci", "isca", "iamo", "ite", "iscano"]),
+            ],
+
+            _ => Vec::new(),
+        }
+    }
+
+    /// Joins a stem and an ending, applying the spelling changes that keep a
+    /// regular verb's pronunciation, e.g. "commençons" or "busqué".
+    fn join(&self, base: &str, ending: &str) -> String {
+        if ending == "-" {
+            return String::new();
+        }
+
+        let starts_with = |letters: &[char]| ending.starts_with(letters);
+        let mut base = base.to_string();
+
+        match self.class {
+            VerbClass::FrenchEr if base == self.stem && starts_with(&['a', 'â', 'o']) => {
+                if base.ends_with('c') {
+                    base.pop();
+                    base.push('ç');
+                } else if base.ends_with('g') {
+                    base.push('e');
+                }
+            }
+            VerbClass::SpanishAr if base == self.stem && starts_with(&['e', 'é']) => {
+                if base.ends_with('c') {
+                    base.pop();
+                    base.push_str("qu");
+                } else if base.ends_with('g') {
+                    base.push('u');
+                } else if base.ends_with('z') {
+                    base.pop();
+                    base.push('c');
+                }
+            }
+            // coger: cojo, coja
+            VerbClass::SpanishEr | VerbClass::SpanishIr
+                if base == self.stem && base.ends_with('g') && starts_with(&['a', 'o']) => {
+                base.pop();
+                base.push('j');
+            }
+            VerbClass::ItalianAre if starts_with(&['e', 'i']) => {
+                if base.ends_with('c') || base.ends_with('g') {
+                    base.push('h');
+                } else if base.ends_with('i') {
+                    // mangiare: mangi, mangerò
+                    base.pop();
+                }
+            }
+            _ => {}
+        }
+
+        format!("{base}{ending}")
+    }
+
+    /// Rows of a table whose forms break the regular pattern.
+    pub fn irregular_rows(&self, table: &ConjugationTable) -> Vec<usize> {
+        table.forms
+            .iter()
+            .enumerate()
+            .filter(|(_, form)| self.is_irregular(table, form) == Some(true))
+            .map(|(row, _)| row)
+            .collect()
+    }
+
+    /// Whether a scraped form differs from what the regular pattern gives,
+    /// or None when there is no rule to check it against.
+    pub fn is_irregular(&self, table: &ConjugationTable, form: &ConjugatedForm) -> Option<bool> {
+        let person = form.person?;
+        let tense = match (table.mood, table.tense_kind) {
+            (Mood::Imperative, Tense::Unknown) => Tense::Present,
+            (_, tense) => tense,
+        };
+
+        let expected = self.forms(table.mood, tense, person);
+        if expected.is_empty() {
+            return None;
+        }
+
+        let scraped = match tense.is_compound() {
+            true => form.participle.clone()?,
+            false => form.form.clone(),
+        };
+
+        // accents count: "achète" is irregular next to a regular "achete"
+        let is_regular = alternatives(&scraped)
+            .iter()
+            .any(|alternative| expected.iter().any(|x| x.to_lowercase() == alternative.to_lowercase()));
+
+        Some(!is_regular)
+    }
+}
+
+/// Reflexive infinitives such as "se lever" or "levantarse" conjugate like
+/// their base verb.
+pub fn base_infinitive(language: &str, infinitive: &str) -> String {
+    let infinitive = infinitive.trim().to_lowercase();
+
+    infinitive
+        .strip_prefix("se ")
+        .or_else(|| infinitive.strip_prefix("s'"))
+        .or_else(|| infinitive.strip_suffix("se").filter(|_| language == "spanish"))
+        .unwrap_or(&infinitive)
+        .to_string()
+}
+
+/// Splits a scraped cell into the forms it lists, dropping what surrounds
+/// the verb itself: "que je", feminine endings like "allé(e)" and "¡!".
+pub fn alternatives(cell: &str) -> Vec<String> {
+    cell.split(',')
+        .flat_map(|part| part.split(" o "))
+        .flat_map(split_slashes)
+        .map(|part| {
+            let part = part.trim();
+            let part = part.strip_prefix("que ").unwrap_or(part);
+            let part = part.strip_prefix("qu'").unwrap_or(part);
+            let part = part.split('(').next().unwrap_or(part);
+
+            part.trim_matches(|c: char| c == '¡' || c == '!' || c.is_whitespace())
+                .to_string()
+        })
+        .filter(|part| !part.is_empty())
+        .collect()
+}
+
+/// "sono andato/a" and "andati/e" give agreement endings after the slash,
+/// which are dropped like "(e)"; only whole forms on both sides are split.
+fn split_slashes(part: &str) -> Vec<&str> {
+    part.split('/')
+        .enumerate()
+        .filter(|(index, piece)| *index == 0 || !matches!(piece.trim(), "a" | "e" | "i"))
+        .map(|(_, piece)| piece)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn forms(language: &str, verb: &str, mood: Mood, tense: Tense) -> Vec<String> {
+        let conjugator = RegularConjugator::new(language, verb).expect("regular verb");
+        Person::ALL
+            .iter()
+            .map(|person| conjugator.forms(mood, tense, *person).join("/"))
+            .collect()
+    }
+
+    #[test]
+    fn present_tense_of_each_class() {
+        let expected = [
+            ("french", "parler", "parle parles parle parlons parlez parlent"),
+            ("french", "finir", "finis finis finit finissons finissez finissent"),
+            ("french", "vendre", "vends vends vend vendons vendez vendent"),
+            ("spanish", "hablar", "hablo hablas habla hablamos habláis hablan"),
+            ("spanish", "comer", "como comes come comemos coméis comen"),
+            ("spanish", "vivir", "vivo vives vive vivimos vivís viven"),
+            ("italian", "parlare", "parlo parli parla parliamo parlate parlano"),
+            ("italian", "temere", "temo temi teme temiamo temete temono"),
+            ("italian", "dormire", "dormo/dormisco dormi/dormisci dorme/dormisce dormiamo/dormiamo dormite/dormite dormono/dormiscono"),
+        ];
+
+        for (language, verb, present) in expected {
+            assert_eq!(forms(language, verb, Mood::Indicative, Tense::Present).join(" "), present, "{verb}");
+        }
+    }
+
+    #[test]
+    fn other_tenses_build_on_the_stem_or_infinitive() {
+        assert_eq!(forms("french", "finir", Mood::Indicative, Tense::Future)[3], "finirons");
+        assert_eq!(forms("french", "vendre", Mood::Conditional, Tense::Present)[0], "vendrais");
+        assert_eq!(forms("spanish", "hablar", Mood::Subjunctive, Tense::Imperfect)[0], "hablara/hablase");
+        assert_eq!(forms("spanish", "vivir", Mood::Indicative, Tense::Preterite)[2], "vivió");
+        assert_eq!(forms("italian", "temere", Mood::Indicative, Tense::Imperfect)[5], "temevano");
+        assert_eq!(forms("french", "parler", Mood::Imperative, Tense::Present)[0], "");
+        assert_eq!(forms("french", "parler", Mood::Imperative, Tense::Present)[1], "parle");
+        assert_eq!(RegularConjugator::new("italian", "dormire").unwrap().past_participle(), "dormito");
+    }
+
+    #[test]
+    fn spelling_changes_keep_the_pronunciation() {
+        assert_eq!(forms("french", "commencer", Mood::Indicative, Tense::Present)[3], "commençons");
+        assert_eq!(forms("french", "manger", Mood::Indicative, Tense::Imperfect)[0], "mangeais");
+        assert_eq!(forms("french", "manger", Mood::Indicative, Tense::Imperfect)[3], "mangions");
+        assert_eq!(forms("spanish", "buscar", Mood::Indicative, Tense::Preterite)[0], "busqué");
+        assert_eq!(forms("spanish", "llegar", Mood::Subjunctive, Tense::Present)[0], "llegue");
+        assert_eq!(forms("spanish", "empezar", Mood::Subjunctive, Tense::Present)[3], "empecemos");
+        assert_eq!(forms("spanish", "coger", Mood::Indicative, Tense::Present)[0], "cojo");
+        assert_eq!(forms("spanish", "dirigir", Mood::Subjunctive, Tense::Present)[0], "dirija");
+        assert_eq!(forms("spanish", "dirigir", Mood::Indicative, Tense::Present)[1], "diriges");
+        assert_eq!(forms("italian", "cercare", Mood::Indicative, Tense::Present)[1], "cerchi");
+        assert_eq!(forms("italian", "pagare", Mood::Indicative, Tense::Future)[0], "pagherò");
+        assert_eq!(forms("italian", "mangiare", Mood::Indicative, Tense::Present)[1], "mangi");
+        assert_eq!(forms("italian", "mangiare", Mood::Indicative, Tense::Future)[0], "mangerò");
+    }
+
+    #[test]
+    fn reflexive_infinitives_conjugate_like_their_base_verb() {
+        assert_eq!(forms("french", "se laver", Mood::Indicative, Tense::Present)[0], "lave");
+        assert_eq!(forms("spanish", "levantarse", Mood::Indicative, Tense::Present)[0], "levanto");
+    }
+}
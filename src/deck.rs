@@ -0,0 +1,221 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::{
+    conjugations::VerbConjugations,
+    definitions::WordDefinitions,
+};
+
+const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+/// A flashcard in the spaced-repetition deck, scheduled with SM-2.
+#[derive(Clone, Debug)]
+pub struct Card {
+    pub id: Option<i64>, // None until stored
+    pub language: String,
+    pub kind: String, // "def", "trans" or "conj", the lookup it came from
+    pub front: String,
+    pub back: String,
+    pub ease: f64,
+    pub interval: i64, // days
+    pub repetitions: i64,
+    pub due: i64, // unix seconds
+}
+
+impl Card {
+    fn new(language: &str, kind: &str, front: String, back: String) -> Card {
+        Card {
+            id: None,
+            language: language.to_string(),
+            kind: kind.to_string(),
+            front,
+            back,
+            ease: 2.5,
+            interval: 0,
+            repetitions: 0,
+            due: now(),
+        }
+    }
+
+    /// A card asking for the first tense shown, usually the present.
+    pub fn from_conjugations(conjugations: &VerbConjugations, language: &str) -> Option<Card> {
+        let table = conjugations.conjugation_tables.first()?;
+        let back = table.forms
+            .iter()
+            .map(|form| format!("{} {}", form.pronoun, form.form))
+            .collect::<Vec<String>>()
+            .join(", ");
+
+        match back.is_empty() {
+            true => None,
+            false => Some(Card::new(
+                language,
+                "conj",
+                format!("{}: {}", conjugations.verb, table.label()),
+                back,
+            )),
+        }
+    }
+
+    /// A card asking for the first few translations of the word.
+    pub fn from_definitions(definitions: &WordDefinitions, kind: &str, language: &str) -> Option<Card> {
+        if definitions.word.is_empty() {
+            return None;
+        }
+
+        let mut translations: Vec<String> = Vec::new();
+        let rows = definitions.definitions
+            .first()?
+            .definitions
+            .iter()
+            .filter_map(|row| row.get(1))
+            .map(|translation| translation.trim().to_string());
+
+        for translation in rows {
+            if !translation.is_empty() && !translations.contains(&translation) {
+                translations.push(translation);
+            }
+        }
+        translations.truncate(3);
+
+        match translations.is_empty() {
+            true => None,
+            false => Some(Card::new(language, kind, definitions.word.clone(), translations.join("; "))),
+        }
+    }
+
+    /// Reschedules the card after a review graded 1 (again), 2 (hard),
+    /// 3 (good) or 4 (easy), mapped onto the SM-2 qualities 1, 3, 4 and 5.
+    pub fn schedule(&mut self, grade: u8, reviewed_at: i64) {
+        let quality = match grade {
+            1 => 1.0,
+            2 => 3.0,
+            3 => 4.0,
+            _ => 5.0,
+        };
+
+        if quality < 3.0 {
+            self.repetitions = 0;
+            self.interval = 1;
+        } else {
+            self.repetitions += 1;
+            self.interval = match self.repetitions {
+                1 => 1,
+                2 => 6,
+                _ => (self.interval as f64 * self.ease).round() as i64,
+            };
+        }
+
+        self.ease = (self.ease + 0.1 - (5.0 - quality) * (0.08 + (5.0 - quality) * 0.02)).max(1.3);
+        self.due = reviewed_at + self.interval * SECONDS_PER_DAY;
+    }
+}
+
+pub fn now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+/// A review session over the cards that are due.
+pub struct Review {
+    pub cards: Vec<Card>,
+    pub current: usize,
+    pub revealed: bool,
+    pub reviewed: usize,
+}
+
+impl Review {
+    pub fn new(cards: Vec<Card>) -> Review {
+        Review {
+            cards,
+            current: 0,
+            revealed: false,
+            reviewed: 0,
+        }
+    }
+
+    pub fn card(&self) -> Option<&Card> {
+        self.cards.get(self.current)
+    }
+
+    /// Grades the current card and moves to the next, returning the
+    /// rescheduled card to store.
+    pub fn grade(&mut self, grade: u8) -> Option<Card> {
+        let mut card = self.cards.get(self.current)?.clone();
+        card.schedule(grade, now());
+
+        self.current += 1;
+        self.reviewed += 1;
+        self.revealed = false;
+        Some(card)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn card() -> Card {
+        Card::new("french", "def", "chat".to_string(), "cat".to_string())
+    }
+
+    #[test]
+    fn good_reviews_space_out_after_one_and_six_days() {
+        let mut card = card();
+
+        card.schedule(3, 0);
+        assert_eq!((card.repetitions, card.interval, card.due), (1, 1, SECONDS_PER_DAY));
+
+        card.schedule(3, 0);
+        assert_eq!((card.repetitions, card.interval), (2, 6));
+
+        card.schedule(3, 0);
+        assert_eq!((card.repetitions, card.interval), (3, 15));
+        assert!((card.ease - 2.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn easy_and_hard_reviews_move_the_ease() {
+        let mut easy = card();
+        easy.schedule(4, 0);
+        assert!((easy.ease - 2.6).abs() < 1e-9);
+
+        let mut hard = card();
+        hard.schedule(2, 0);
+        assert!((hard.ease - 2.36).abs() < 1e-9);
+        assert_eq!(hard.interval, 1);
+    }
+
+    #[test]
+    fn a_lapse_starts_the_card_over() {
+        let mut card = card();
+        for _ in 0..3 {
+            card.schedule(3, 0);
+        }
+
+        card.schedule(1, 100);
+        assert_eq!((card.repetitions, card.interval), (0, 1));
+        assert_eq!(card.due, 100 + SECONDS_PER_DAY);
+        assert!((card.ease - 1.96).abs() < 1e-9);
+    }
+
+    #[test]
+    fn the_ease_never_drops_below_1_3() {
+        let mut card = card();
+        for _ in 0..10 {
+            card.schedule(1, 0);
+        }
+        assert!((card.ease - 1.3).abs() < 1e-9);
+    }
+
+    #[test]
+    fn grading_moves_to_the_next_card_and_hides_the_answer() {
+        let mut review = Review::new(vec![card(), card()]);
+        review.revealed = true;
+
+        let graded = review.grade(4).expect("a card to grade");
+        assert_eq!(graded.repetitions, 1);
+        assert_eq!((review.current, review.reviewed, review.revealed), (1, 1, false));
+    }
+}
@@ -1,7 +1,11 @@
 
-use reqwest::{self, Client};
 use scraper::{Html, ElementRef};
-use crate::{wordreference::wordreference_utils, user_error::UserError};
+use crate::{
+    app_error::AppError,
+    http::{FetchError, HttpClient},
+    status::{LoadStage, Progress},
+    wordreference::wordreference_utils,
+};
 use serde::{Serialize, Deserialize};
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -13,6 +17,9 @@ pub struct DefinitionTable {
 #[derive(Serialize, Deserialize, Clone)]
 pub struct WordDefinitions {
     pub title: String,
+    // the word looked up, missing from rows cached before it was kept
+    #[serde(default)]
+    pub word: String,
     pub definitions: Vec<DefinitionTable>,
 }
 
@@ -20,6 +27,7 @@ impl WordDefinitions {
     pub fn empty() -> Self {
         Self {
             title: "".to_string(),
+            word: "".to_string(),
             definitions: Vec::new(),
         }
     }
@@ -27,8 +35,8 @@ impl WordDefinitions {
     fn extract_definitions_from_table(
         table: Html,
         to_language: String,
-    ) -> Result<DefinitionTable, UserError> {
-        let invalid_parsing_error = UserError {
+    ) -> Result<DefinitionTable, AppError> {
+        let invalid_parsing_error = AppError::Parse {
             message: format!(
                 "Translations to '{to_language}' could not be found for the word. \
                 Please double check your spelling",
@@ -40,7 +48,7 @@ impl WordDefinitions {
         )
             .expect("td.FrWrd and td.ToWrd exist");
 
-        let language_code = wordreference_utils::map_language(to_language);
+        let language_code = wordreference_utils::map_language(to_language.clone());
         let selector_query = format!(
             "em[data-lang=\"{0}\"], span[data-ph=\"sLang_{0}\"]",
             language_code,
@@ -48,7 +56,9 @@ impl WordDefinitions {
         let from_query = scraper::Selector::parse(
             selector_query.as_str(),
         )
-            .expect("todo");
+            .map_err(|_err| AppError::Parse {
+                message: format!("'{to_language}' has no language code to select translations by"),
+            })?;
 
         let words = table
             .select(&from_word_to_word_query)
@@ -65,11 +75,11 @@ impl WordDefinitions {
                 .text()
                 .collect::<String>(); // take first element instead?
 
-            if a.len() == 0 {
+            if a.is_empty() {
                 from_words.push(b);
                 to_words.push(Vec::new());
             } else {
-                if to_words.len() != 0 {
+                if !to_words.is_empty() {
                     let l = to_words.last_mut().unwrap();
                     l.push(b);
                 } else {
@@ -134,39 +144,29 @@ impl WordDefinitions {
         word: String,
         from_language: String,
         to_language: String,
-        client: &Client,
-    ) -> Result<Vec<Html>, UserError> {
-        let not_exist_error = UserError {
+        client: &HttpClient,
+        progress: &Progress,
+    ) -> Result<Vec<Html>, AppError> {
+        let not_exist_error = AppError::NotFound {
             message: format!(
                 "The word '{word}' does not exist in the selected language \
                 ({from_language}). Please double check your spelling",
             )
         };
 
-        let network_error = UserError {
-            message: "Could not find the corresponding definitions, \
-            please check your network connection and spelling".to_string(),
-        };
-
         let word_query_url = wordreference_utils::definition_url(
+            client.base_url(),
             from_language.clone(),
             to_language.clone(),
             word.clone(),
         );
 
-        let response = match match client.get(
-                word_query_url,
-            )
-            .send()
-            .await {
-                Ok(it) => it,
-                Err(_err) => return Err(network_error),
-            }
-            .text()
-            .await {
-                Ok(it) => it,
-                Err(_err) => return Err(not_exist_error),
-            };
+        let response = match client.get_text(&word_query_url, progress).await {
+            Ok(response) => response,
+            Err(FetchError::Status { status: 404, .. }) => return Err(not_exist_error),
+            Err(err) => return Err(err.into()),
+        };
+        progress(LoadStage::Parsing);
 
         let document = scraper::Html::parse_document(&response);
         let table_query = scraper::Selector::parse("table.WRD")
@@ -186,9 +186,10 @@ impl WordDefinitions {
         to_language: String,
         from_language: String,
         word: String,
-        client: &Client,
-    ) -> Result<WordDefinitions, UserError> {
-        let not_exist_error = UserError {
+        client: &HttpClient,
+        progress: &Progress,
+    ) -> Result<WordDefinitions, AppError> {
+        let not_exist_error = AppError::NotFound {
             message: format!(
                 "The word '{word}' does not exist in the selected language \
                 ({from_language}). Please double check your spelling",
@@ -200,6 +201,7 @@ impl WordDefinitions {
             from_language.clone(),
             to_language.clone(),
             client,
+            progress,
         ).await;
 
         match tables_result {
@@ -212,13 +214,14 @@ impl WordDefinitions {
                     to_language.clone(),
                 );
 
-                if definitions.len() == 0 {
+                if definitions.is_empty() {
                     return Err(not_exist_error);
                 }
 
                 let title = format!("Translate '{word}' to {to_language}");
                 let word_definitions = WordDefinitions {
                     title,
+                    word,
                     definitions,
                 };
 
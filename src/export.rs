@@ -0,0 +1,440 @@
+use std::{collections::HashSet, fs, io::Write, path::Path};
+
+use rusqlite::{params, Connection};
+use serde_json::json;
+use sha1::{Digest, Sha1};
+use zip::{write::FileOptions, ZipWriter};
+
+use crate::{
+    conjugations::VerbConjugations,
+    deck,
+    definitions::WordDefinitions,
+    user_error::UserError,
+};
+
+// fixed ids so importing a newer export updates the notes of an older one
+const TRANSLATION_MODEL_ID: i64 = 1_683_021_442_001;
+const CONJUGATION_MODEL_ID: i64 = 1_683_021_442_002;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum NoteKind {
+    Translation, // fields: word, translations
+    Conjugation, // fields: verb, tense, conjugation
+}
+
+/// One exported flashcard, its last field holding one line per row.
+pub struct Note {
+    pub kind: NoteKind,
+    pub fields: Vec<String>,
+    pub lines: Vec<String>,
+    pub tag: String,
+}
+
+impl Note {
+    /// Every distinct translation of a word, or None if it has none.
+    pub fn from_definitions(definitions: &WordDefinitions, tag: &str) -> Option<Note> {
+        let mut lines: Vec<String> = Vec::new();
+        let translations = definitions.definitions
+            .iter()
+            .flat_map(|table| table.definitions.iter())
+            .filter_map(|row| row.get(1))
+            .map(|translation| translation.trim().to_string());
+
+        for translation in translations {
+            if !translation.is_empty() && !lines.contains(&translation) {
+                lines.push(translation);
+            }
+        }
+
+        match definitions.word.is_empty() || lines.is_empty() {
+            true => None,
+            false => Some(Note {
+                kind: NoteKind::Translation,
+                fields: vec![definitions.word.clone()],
+                lines,
+                tag: tag.to_string(),
+            }),
+        }
+    }
+
+    /// A note per tense of the verb.
+    pub fn from_conjugations(conjugations: &VerbConjugations, tag: &str) -> Vec<Note> {
+        conjugations.conjugation_tables
+            .iter()
+            .filter(|table| !table.forms.is_empty())
+            .map(|table| Note {
+                kind: NoteKind::Conjugation,
+                fields: vec![conjugations.verb.clone(), table.label()],
+                lines: table.forms
+                    .iter()
+                    .map(|form| format!("{} {}", form.pronoun, form.form).trim().to_string())
+                    .collect(),
+                tag: tag.to_string(),
+            })
+            .collect()
+    }
+
+    /// The question side as plain text, e.g. "parler (indicatif présent)".
+    fn front(&self) -> String {
+        match self.fields.as_slice() {
+            [verb, tense] => format!("{verb} ({tense})"),
+            fields => fields.join(" "),
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub enum ExportFormat {
+    Anki,
+    Csv,
+    Tsv,
+}
+
+impl ExportFormat {
+    pub fn parse(format: &str) -> Option<ExportFormat> {
+        match format.to_lowercase().as_str() {
+            "anki" | "apkg" => Some(ExportFormat::Anki),
+            "csv" => Some(ExportFormat::Csv),
+            "tsv" => Some(ExportFormat::Tsv),
+            _ => None,
+        }
+    }
+}
+
+/// Writes the notes to a file in the given format.
+pub fn export(notes: &[Note], format: ExportFormat, path: &Path, deck_name: &str) -> Result<(), UserError> {
+    let write_error = |err: String| UserError {
+        message: format!("Could not write '{}': {err}", path.display()),
+    };
+
+    match format {
+        ExportFormat::Csv => fs::write(path, delimited(notes, ',')).map_err(|err| write_error(err.to_string())),
+        ExportFormat::Tsv => fs::write(path, delimited(notes, '\t')).map_err(|err| write_error(err.to_string())),
+        ExportFormat::Anki => write_apkg(notes, path, deck_name).map_err(write_error),
+    }
+}
+
+/// "front,back,tags" rows, which both Anki and Quizlet import. Rows of the
+/// back are separated by "; " since Quizlet has no multiline fields.
+fn delimited(notes: &[Note], delimiter: char) -> String {
+    let escape = |field: String| match delimiter {
+        '\t' => field.replace(['\t', '\n'], " "),
+        _ if field.contains([delimiter, '"', '\n']) => format!("\"{}\"", field.replace('"', "\"\"")),
+        _ => field,
+    };
+
+    notes
+        .iter()
+        .map(|note| {
+            [note.front(), note.lines.join("; "), note.tag.clone()]
+                .into_iter()
+                .map(escape)
+                .collect::<Vec<String>>()
+                .join(&delimiter.to_string())
+        })
+        .map(|row| row + "\n")
+        .collect()
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+fn sha1_hex(text: &str) -> String {
+    Sha1::digest(text.as_bytes())
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+/// An .apkg is a zip of an Anki collection database along with a media
+/// index, here empty.
+fn write_apkg(notes: &[Note], path: &Path, deck_name: &str) -> Result<(), String> {
+    // removed when dropped, and never shared with another export in progress
+    let collection_file = tempfile::Builder::new()
+        .prefix("transrustate-")
+        .suffix(".anki2")
+        .tempfile()
+        .map_err(|err| err.to_string())?;
+
+    let collection = Connection::open(collection_file.path())
+        .and_then(|connection| {
+            write_collection(&connection, notes, deck_name)?;
+            connection.close().map_err(|(_, err)| err)
+        })
+        .map_err(|err| err.to_string())
+        .and_then(|_| fs::read(collection_file.path()).map_err(|err| err.to_string()))?;
+
+    let file = fs::File::create(path).map_err(|err| err.to_string())?;
+    let mut zip = ZipWriter::new(file);
+    let options = FileOptions::default();
+
+    zip.start_file("collection.anki2", options).map_err(|err| err.to_string())?;
+    zip.write_all(&collection).map_err(|err| err.to_string())?;
+    zip.start_file("media", options).map_err(|err| err.to_string())?;
+    zip.write_all(b"{}").map_err(|err| err.to_string())?;
+    zip.finish().map_err(|err| err.to_string())?;
+
+    Ok(())
+}
+
+/// Creates the tables of an Anki 2.1 collection (schema 11) and fills them
+/// with a deck of new cards.
+fn write_collection(connection: &Connection, notes: &[Note], deck_name: &str) -> rusqlite::Result<()> {
+    connection.execute_batch(
+        "CREATE TABLE col (
+            id INTEGER PRIMARY KEY, crt INTEGER NOT NULL, mod INTEGER NOT NULL,
+            scm INTEGER NOT NULL, ver INTEGER NOT NULL, dty INTEGER NOT NULL,
+            usn INTEGER NOT NULL, ls INTEGER NOT NULL, conf TEXT NOT NULL,
+            models TEXT NOT NULL, decks TEXT NOT NULL, dconf TEXT NOT NULL,
+            tags TEXT NOT NULL
+        );
+        CREATE TABLE notes (
+            id INTEGER PRIMARY KEY, guid TEXT NOT NULL, mid INTEGER NOT NULL,
+            mod INTEGER NOT NULL, usn INTEGER NOT NULL, tags TEXT NOT NULL,
+            flds TEXT NOT NULL, sfld INTEGER NOT NULL, csum INTEGER NOT NULL,
+            flags INTEGER NOT NULL, data TEXT NOT NULL
+        );
+        CREATE TABLE cards (
+            id INTEGER PRIMARY KEY, nid INTEGER NOT NULL, did INTEGER NOT NULL,
+            ord INTEGER NOT NULL, mod INTEGER NOT NULL, usn INTEGER NOT NULL,
+            type INTEGER NOT NULL, queue INTEGER NOT NULL, due INTEGER NOT NULL,
+            ivl INTEGER NOT NULL, factor INTEGER NOT NULL, reps INTEGER NOT NULL,
+            lapses INTEGER NOT NULL, left INTEGER NOT NULL, odue INTEGER NOT NULL,
+            odid INTEGER NOT NULL, flags INTEGER NOT NULL, data TEXT NOT NULL
+        );
+        CREATE TABLE revlog (
+            id INTEGER PRIMARY KEY, cid INTEGER NOT NULL, usn INTEGER NOT NULL,
+            ease INTEGER NOT NULL, ivl INTEGER NOT NULL, lastIvl INTEGER NOT NULL,
+            factor INTEGER NOT NULL, time INTEGER NOT NULL, type INTEGER NOT NULL
+        );
+        CREATE TABLE graves (
+            usn INTEGER NOT NULL, oid INTEGER NOT NULL, type INTEGER NOT NULL
+        );
+        CREATE INDEX ix_notes_usn ON notes (usn);
+        CREATE INDEX ix_cards_usn ON cards (usn);
+        CREATE INDEX ix_revlog_usn ON revlog (usn);
+        CREATE INDEX ix_cards_nid ON cards (nid);
+        CREATE INDEX ix_cards_sched ON cards (did, queue, due);
+        CREATE INDEX ix_revlog_cid ON revlog (cid);
+        CREATE INDEX ix_notes_csum ON notes (csum);",
+    )?;
+
+    let now = deck::now();
+    let now_ms = now * 1000;
+    // a deck id of its own per name, so decks of several languages can coexist
+    let deck_id = i64::from_str_radix(&sha1_hex(deck_name)[..10], 16).unwrap_or(now_ms);
+
+    let models = json!({
+        TRANSLATION_MODEL_ID.to_string(): model(
+            TRANSLATION_MODEL_ID,
+            "transrustate translation",
+            &["Word", "Translation"],
+            "{{Word}}",
+            "{{FrontSide}}<hr id=answer>{{Translation}}",
+            deck_id,
+            now,
+        ),
+        CONJUGATION_MODEL_ID.to_string(): model(
+            CONJUGATION_MODEL_ID,
+            "transrustate conjugation",
+            &["Verb", "Tense", "Conjugation"],
+            "{{Verb}}<br><i>{{Tense}}</i>",
+            "{{FrontSide}}<hr id=answer>{{Conjugation}}",
+            deck_id,
+            now,
+        ),
+    });
+
+    let deck = |id: i64, name: &str| json!({
+        "id": id,
+        "name": name,
+        "desc": "",
+        "conf": 1,
+        "dyn": 0,
+        "collapsed": false,
+        "extendNew": 10,
+        "extendRev": 50,
+        "usn": -1,
+        "mod": now,
+        "newToday": [0, 0],
+        "revToday": [0, 0],
+        "lrnToday": [0, 0],
+        "timeToday": [0, 0],
+    });
+    let decks = json!({
+        "1": deck(1, "Default"),
+        deck_id.to_string(): deck(deck_id, deck_name),
+    });
+
+    let dconf = json!({
+        "1": {
+            "id": 1,
+            "name": "Default",
+            "replayq": true,
+            "lapse": { "delays": [10], "mult": 0, "minInt": 1, "leechFails": 8, "leechAction": 0 },
+            "rev": { "perDay": 100, "fuzz": 0.05, "ivlFct": 1, "maxIvl": 36500, "ease4": 1.3, "bury": true, "minSpace": 1 },
+            "new": { "delays": [1, 10], "ints": [1, 4, 7], "initialFactor": 2500, "separate": true, "order": 1, "perDay": 20, "bury": true },
+            "timer": 0,
+            "maxTaken": 60,
+            "usn": 0,
+            "mod": 0,
+            "autoplay": true,
+            "dyn": false,
+        },
+    });
+
+    let conf = json!({
+        "activeDecks": [1],
+        "curDeck": deck_id,
+        "newSpread": 0,
+        "collapseTime": 1200,
+        "timeLim": 0,
+        "estTimes": true,
+        "dueCounts": true,
+        "curModel": TRANSLATION_MODEL_ID,
+        "nextPos": notes.len() + 1,
+        "sortType": "noteFld",
+        "sortBackwards": false,
+        "addToCur": true,
+    });
+
+    connection.execute(
+        "INSERT INTO col VALUES (1, ?1, ?2, ?2, 11, 0, 0, 0, ?3, ?4, ?5, ?6, '{}')",
+        params![now, now_ms, conf.to_string(), models.to_string(), decks.to_string(), dconf.to_string()],
+    )?;
+
+    let mut used_ids: HashSet<i64> = HashSet::new();
+    for (position, note) in notes.iter().enumerate() {
+        let model_id = match note.kind {
+            NoteKind::Translation => TRANSLATION_MODEL_ID,
+            NoteKind::Conjugation => CONJUGATION_MODEL_ID,
+        };
+
+        let mut fields = note.fields
+            .iter()
+            .map(|field| escape_html(field))
+            .collect::<Vec<String>>();
+        fields.push(
+            note.lines
+                .iter()
+                .map(|line| escape_html(line))
+                .collect::<Vec<String>>()
+                .join("<br>"),
+        );
+
+        // the checksum Anki finds duplicates by, from the sort field
+        let csum = i64::from_str_radix(&sha1_hex(&note.fields[0])[..8], 16).unwrap_or(0);
+        let guid = sha1_hex(&format!("{model_id}:{}", note.front()))[..10].to_string();
+        let id = note_id(&guid, &mut used_ids);
+
+        connection.execute(
+            "INSERT INTO notes VALUES (?1, ?2, ?3, ?4, -1, ?5, ?6, ?7, ?8, 0, '')",
+            params![id, guid, model_id, now, format!(" {} ", note.tag), fields.join("\x1f"), fields[0], csum],
+        )?;
+        connection.execute(
+            "INSERT INTO cards VALUES (?1, ?1, ?2, 0, ?3, -1, 0, 0, ?4, 0, 0, 0, 0, 0, 0, 0, 0, '')",
+            params![id, deck_id, now, position as i64 + 1],
+        )?;
+    }
+
+    Ok(())
+}
+
+/// A note id derived from the note's guid, so exporting a note again gives
+/// it the same id rather than one another export may already have used.
+fn note_id(guid: &str, used_ids: &mut HashSet<i64>) -> i64 {
+    let mut id = i64::from_str_radix(&sha1_hex(guid)[..12], 16).unwrap_or(1);
+    while !used_ids.insert(id) {
+        id += 1;
+    }
+
+    id
+}
+
+/// A basic note type with a single card template.
+fn model(
+    id: i64,
+    name: &str,
+    field_names: &[&str],
+    question: &str,
+    answer: &str,
+    deck_id: i64,
+    now: i64,
+) -> serde_json::Value {
+    let fields = field_names
+        .iter()
+        .enumerate()
+        .map(|(ord, field_name)| json!({
+            "name": field_name,
+            "ord": ord,
+            "sticky": false,
+            "rtl": false,
+            "font": "Arial",
+            "size": 20,
+            "media": [],
+        }))
+        .collect::<Vec<serde_json::Value>>();
+
+    json!({
+        "id": id,
+        "name": name,
+        "type": 0,
+        "mod": now,
+        "usn": -1,
+        "sortf": 0,
+        "did": deck_id,
+        "tmpls": [{
+            "name": "Card 1",
+            "ord": 0,
+            "qfmt": question,
+            "afmt": answer,
+            "did": null,
+            "bqfmt": "",
+            "bafmt": "",
+        }],
+        "flds": fields,
+        "css": ".card { font-family: arial; font-size: 20px; text-align: center; color: black; background-color: white; }",
+        "latexPre": "\\documentclass[12pt]{article}\n\\special{papersize=3in,5in}\n\\usepackage[utf8]{inputenc}\n\\usepackage{amssymb,amsmath}\n\\pagestyle{empty}\n\\setlength{\\parindent}{0in}\n\\begin{document}\n",
+        "latexPost": "\\end{document}",
+        "tags": [],
+        "vers": [],
+        "req": [[0, "any", [0]]],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn note_ids_are_stable_and_distinct() {
+        let mut used_ids = HashSet::new();
+        let first = note_id("abcdef0123", &mut used_ids);
+        assert_eq!(note_id("abcdef0123", &mut HashSet::new()), first);
+        assert_ne!(note_id("0123abcdef", &mut used_ids), first);
+
+        // a repeated guid still gets an id of its own within one collection
+        assert_eq!(note_id("abcdef0123", &mut used_ids), first + 1);
+    }
+
+    #[test]
+    fn exports_in_a_row_use_separate_collections() {
+        let notes = vec![Note {
+            kind: NoteKind::Translation,
+            fields: vec!["chat".to_string()],
+            lines: vec!["cat".to_string()],
+            tag: "french".to_string(),
+        }];
+
+        let dir = tempfile::tempdir().expect("temp dir");
+        let first = dir.path().join("first.apkg");
+        let second = dir.path().join("second.apkg");
+        write_apkg(&notes, &first, "transrustate").expect("first export");
+        write_apkg(&notes, &second, "transrustate").expect("second export");
+
+        assert!(first.exists() && second.exists());
+    }
+}
@@ -0,0 +1,522 @@
+use crate::{
+    conjugations::{ConjugationTable, VerbConjugations},
+    conjugator::{RegularConjugator, VerbClass},
+    grammar::{Mood, Person, Tense},
+    verb_models::{self, Forms, VerbModel},
+};
+
+/// Where the forms of a generated table come from.
+#[derive(Clone, Copy)]
+enum TableSource {
+    Simple(Mood, Tense),
+    // auxiliary conjugated in the given mood and tense, then the participle
+    Compound(Mood, Tense),
+    // the spanish "-se" form of the imperfect subjunctive
+    SeImperfect,
+}
+
+type TableLayout = [(&'static str, &'static str, TableSource)];
+
+static FRENCH_TABLES: &TableLayout = &[
+    ("indicatif", "présent", TableSource::Simple(Mood::Indicative, Tense::Present)),
+    ("indicatif", "passé composé", TableSource::Compound(Mood::Indicative, Tense::Present)),
+    ("indicatif", "imparfait", TableSource::Simple(Mood::Indicative, Tense::Imperfect)),
+    ("indicatif", "plus-que-parfait", TableSource::Compound(Mood::Indicative, Tense::Imperfect)),
+    ("indicatif", "passé simple", TableSource::Simple(Mood::Indicative, Tense::Preterite)),
+    ("indicatif", "passé antérieur", TableSource::Compound(Mood::Indicative, Tense::Preterite)),
+    ("indicatif", "futur simple", TableSource::Simple(Mood::Indicative, Tense::Future)),
+    ("indicatif", "futur antérieur", TableSource::Compound(Mood::Indicative, Tense::Future)),
+    ("subjonctif", "présent", TableSource::Simple(Mood::Subjunctive, Tense::Present)),
+    ("subjonctif", "passé", TableSource::Compound(Mood::Subjunctive, Tense::Present)),
+    ("subjonctif", "imparfait", TableSource::Simple(Mood::Subjunctive, Tense::Imperfect)),
+    ("subjonctif", "plus-que-parfait", TableSource::Compound(Mood::Subjunctive, Tense::Imperfect)),
+    ("conditionnel", "présent", TableSource::Simple(Mood::Conditional, Tense::Present)),
+    ("conditionnel", "passé", TableSource::Compound(Mood::Conditional, Tense::Present)),
+    ("impératif", "présent", TableSource::Simple(Mood::Imperative, Tense::Present)),
+];
+
+static SPANISH_TABLES: &TableLayout = &[
+    ("indicativo", "presente", TableSource::Simple(Mood::Indicative, Tense::Present)),
+    ("indicativo", "imperfecto", TableSource::Simple(Mood::Indicative, Tense::Imperfect)),
+    ("indicativo", "pretérito", TableSource::Simple(Mood::Indicative, Tense::Preterite)),
+    ("indicativo", "futuro", TableSource::Simple(Mood::Indicative, Tense::Future)),
+    ("indicativo", "condicional", TableSource::Simple(Mood::Conditional, Tense::Present)),
+    ("indicativo", "pretérito perfecto", TableSource::Compound(Mood::Indicative, Tense::Present)),
+    ("indicativo", "pluscuamperfecto", TableSource::Compound(Mood::Indicative, Tense::Imperfect)),
+    ("indicativo", "pretérito anterior", TableSource::Compound(Mood::Indicative, Tense::Preterite)),
+    ("indicativo", "futuro perfecto", TableSource::Compound(Mood::Indicative, Tense::Future)),
+    ("indicativo", "condicional perfecto", TableSource::Compound(Mood::Conditional, Tense::Present)),
+    ("subjuntivo", "presente", TableSource::Simple(Mood::Subjunctive, Tense::Present)),
+    ("subjuntivo", "imperfecto", TableSource::Simple(Mood::Subjunctive, Tense::Imperfect)),
+    ("subjuntivo", "imperfecto (2)", TableSource::SeImperfect),
+    ("subjuntivo", "futuro", TableSource::Simple(Mood::Subjunctive, Tense::Future)),
+    ("subjuntivo", "pretérito perfecto", TableSource::Compound(Mood::Subjunctive, Tense::Present)),
+    ("subjuntivo", "pluscuamperfecto", TableSource::Compound(Mood::Subjunctive, Tense::Imperfect)),
+    ("imperativo", "presente", TableSource::Simple(Mood::Imperative, Tense::Present)),
+];
+
+static ITALIAN_TABLES: &TableLayout = &[
+    ("indicativo", "presente", TableSource::Simple(Mood::Indicative, Tense::Present)),
+    ("indicativo", "passato prossimo", TableSource::Compound(Mood::Indicative, Tense::Present)),
+    ("indicativo", "imperfetto", TableSource::Simple(Mood::Indicative, Tense::Imperfect)),
+    ("indicativo", "trapassato prossimo", TableSource::Compound(Mood::Indicative, Tense::Imperfect)),
+    ("indicativo", "passato remoto", TableSource::Simple(Mood::Indicative, Tense::Preterite)),
+    ("indicativo", "trapassato remoto", TableSource::Compound(Mood::Indicative, Tense::Preterite)),
+    ("indicativo", "futuro semplice", TableSource::Simple(Mood::Indicative, Tense::Future)),
+    ("indicativo", "futuro anteriore", TableSource::Compound(Mood::Indicative, Tense::Future)),
+    ("congiuntivo", "presente", TableSource::Simple(Mood::Subjunctive, Tense::Present)),
+    ("congiuntivo", "passato", TableSource::Compound(Mood::Subjunctive, Tense::Present)),
+    ("congiuntivo", "imperfetto", TableSource::Simple(Mood::Subjunctive, Tense::Imperfect)),
+    ("congiuntivo", "trapassato", TableSource::Compound(Mood::Subjunctive, Tense::Imperfect)),
+    ("condizionale", "presente", TableSource::Simple(Mood::Conditional, Tense::Present)),
+    ("condizionale", "passato", TableSource::Compound(Mood::Conditional, Tense::Present)),
+    ("imperativo", "presente", TableSource::Simple(Mood::Imperative, Tense::Present)),
+];
+
+fn pronouns(language: &str, mood: Mood) -> [&'static str; 6] {
+    match (language, mood) {
+        ("french", Mood::Imperative) => ["-", "(tu)", "-", "(nous)", "(vous)", "-"],
+        ("french", _) => ["je", "tu", "il, elle, on", "nous", "vous", "ils, elles"],
+        ("spanish", Mood::Imperative) => ["-", "(tú)", "(Ud.)", "(nosotros)", "(vosotros)", "(Uds.)"],
+        ("spanish", _) => ["yo", "tú", "él, ella, Ud.", "nosotros", "vosotros", "ellos, ellas, Uds."],
+        ("italian", Mood::Imperative) => ["-", "(tu)", "(Lei)", "(noi)", "(voi)", "(Loro)"],
+        _ => ["io", "tu", "lui, lei", "noi", "voi", "loro"],
+    }
+}
+
+/// Conjugates a verb without the network, from the irregular model it
+/// follows or else the regular pattern of its class. Returns None for verbs
+/// that match neither.
+pub fn generate_conjugations(language: &str, infinitive: &str) -> Option<VerbConjugations> {
+    let infinitive = infinitive.trim().to_lowercase();
+    let generator = VerbGenerator::new(language, &infinitive)?;
+
+    let auxiliary_infinitive = verb_models::auxiliary_verb(language, &infinitive);
+    let auxiliary = VerbGenerator::new(language, auxiliary_infinitive)?;
+    let takes_be = !matches!(auxiliary_infinitive, "avoir" | "avere" | "haber");
+
+    let layout = match language {
+        "french" => FRENCH_TABLES,
+        "spanish" => SPANISH_TABLES,
+        "italian" => ITALIAN_TABLES,
+        _ => return None,
+    };
+
+    let mut conjugation_tables: Vec<ConjugationTable> = Vec::new();
+    for (mood_label, tense_label, source) in layout {
+        let forms = match *source {
+            TableSource::Simple(mood, tense) => generator.simple(mood, tense),
+            TableSource::SeImperfect => generator.se_imperfect(),
+            TableSource::Compound(mood, tense) => {
+                let participle = generator.past_participle()?;
+                auxiliary.simple(mood, tense).map(|auxiliary_forms| {
+                    Person::ALL.map(|person| {
+                        let index = person_index(person);
+                        match auxiliary_forms[index].is_empty() {
+                            true => String::new(),
+                            false => format!(
+                                "{} {}",
+                                auxiliary_forms[index],
+                                agree(language, &participle, person, takes_be),
+                            ),
+                        }
+                    })
+                })
+            }
+        };
+
+        let Some(forms) = forms else {
+            continue;
+        };
+
+        let mood = match source {
+            TableSource::Simple(mood, _) | TableSource::Compound(mood, _) => *mood,
+            TableSource::SeImperfect => Mood::Subjunctive,
+        };
+        let pairs = pronouns(language, mood)
+            .iter()
+            .zip(forms.iter())
+            .filter(|(pronoun, form)| **pronoun != "-" && !form.is_empty())
+            .map(|(pronoun, form)| (elide(language, pronoun, form), form.clone()))
+            .collect::<Vec<(String, String)>>();
+
+        if !pairs.is_empty() {
+            conjugation_tables.push(ConjugationTable::from_pairs(mood_label, tense_label, pairs));
+        }
+    }
+
+    if conjugation_tables.is_empty() {
+        return None;
+    }
+
+    Some(VerbConjugations {
+        verb: infinitive,
+        language: language.to_string(),
+        conjugation_tables,
+        generated: true,
+        auxiliary: Some(auxiliary_infinitive.to_string()),
+        past_participle: generator.past_participle(),
+        present_participle: generator.present_participle(),
+    })
+}
+
+fn person_index(person: Person) -> usize {
+    Person::ALL
+        .iter()
+        .position(|x| *x == person)
+        .expect("person is one of the six persons")
+}
+
+/// "je" becomes "j'" before a vowel or a mute h.
+fn elide(language: &str, pronoun: &str, form: &str) -> String {
+    let starts_with_vowel = form
+        .starts_with(['a', 'e', 'i', 'o', 'u', 'h', 'â', 'ê', 'é', 'è', 'î', 'ô', 'û']);
+
+    match (language, pronoun) {
+        ("french", "je") if starts_with_vowel => "j'".to_string(),
+        _ => pronoun.to_string(),
+    }
+}
+
+/// Past participles after "être" or "essere" agree with the subject.
+fn agree(language: &str, participle: &str, person: Person, takes_be: bool) -> String {
+    let plural = matches!(person, Person::FirstPlural | Person::SecondPlural | Person::ThirdPlural);
+
+    match (language, takes_be, plural) {
+        ("french", true, false) => format!("{participle}(e)"),
+        ("french", true, true) => format!("{participle}(e)s"),
+        ("italian", true, _) if participle.ends_with('o') => {
+            let stem = &participle[..participle.len() - 1];
+            match plural {
+                false => format!("{stem}o/a"),
+                true => format!("{stem}i/e"),
+            }
+        }
+        _ => participle.to_string(),
+    }
+}
+
+struct VerbGenerator {
+    language: String,
+    infinitive: String,
+    prefix: String,
+    model: Option<&'static VerbModel>,
+    regular: Option<RegularConjugator>,
+}
+
+impl VerbGenerator {
+    fn new(language: &str, infinitive: &str) -> Option<VerbGenerator> {
+        let model = verb_models::find_model(language, infinitive);
+        let regular = RegularConjugator::new(language, infinitive);
+        if model.is_none() && regular.is_none() {
+            return None;
+        }
+
+        let (prefix, model) = match model {
+            Some((prefix, model)) => (prefix, Some(model)),
+            None => (String::new(), None),
+        };
+
+        Some(VerbGenerator {
+            language: language.to_string(),
+            infinitive: infinitive.to_string(),
+            prefix,
+            model,
+            regular,
+        })
+    }
+
+    fn model_forms(&self, forms: Option<Forms>) -> Option<[String; 6]> {
+        forms.map(|forms| {
+            forms.map(|form| match form {
+                "-" => String::new(),
+                _ => format!("{}{form}", self.prefix),
+            })
+        })
+    }
+
+    fn regular_forms(&self, mood: Mood, tense: Tense) -> Option<[String; 6]> {
+        let regular = self.regular.as_ref()?;
+        let forms = Person::ALL.map(|person| {
+            regular.forms(mood, tense, person)
+                .into_iter()
+                .next()
+                .unwrap_or_default()
+        });
+
+        match forms.iter().all(|form| form.is_empty()) {
+            true => None,
+            false => Some(forms),
+        }
+    }
+
+    fn future_stem(&self) -> Option<String> {
+        self.model
+            .and_then(|model| model.future_stem)
+            .map(|future_stem| format!("{}{future_stem}", self.prefix))
+    }
+
+    fn with_endings(stem: &str, endings: [&str; 6]) -> [String; 6] {
+        endings.map(|ending| format!("{stem}{ending}"))
+    }
+
+    fn past_participle(&self) -> Option<String> {
+        match self.model {
+            Some(model) => Some(format!("{}{}", self.prefix, model.past_participle)),
+            None => self.regular.as_ref().map(|regular| regular.past_participle()),
+        }
+    }
+
+    /// The french participle in -ant, or the spanish and italian gerund.
+    fn present_participle(&self) -> Option<String> {
+        if let Some(present_participle) = self.model.and_then(|model| model.present_participle) {
+            return Some(format!("{}{present_participle}", self.prefix));
+        }
+
+        match self.language.as_str() {
+            // "nous finissons" gives "finissant"
+            "french" => {
+                let present = self.simple(Mood::Indicative, Tense::Present)?;
+                present[3].strip_suffix("ons").map(|stem| format!("{stem}ant"))
+            }
+            _ => {
+                let regular = self.regular.as_ref()?;
+                let ending = match regular.class {
+                    VerbClass::SpanishAr => "ando",
+                    VerbClass::SpanishEr | VerbClass::SpanishIr => "iendo",
+                    VerbClass::ItalianAre => "ando",
+                    _ => "endo",
+                };
+                Some(format!("{}{ending}", regular.stem))
+            }
+        }
+    }
+
+    fn simple(&self, mood: Mood, tense: Tense) -> Option<[String; 6]> {
+        let Some(model) = self.model else {
+            return self.regular_forms(mood, tense);
+        };
+
+        let present = self.model_forms(Some(model.present))?;
+        let derived = match (self.language.as_str(), mood, tense) {
+            (_, Mood::Indicative, Tense::Present) => Some(present),
+            (_, Mood::Indicative, Tense::Imperfect) if model.imperfect.is_some() => self.model_forms(model.imperfect),
+            (_, Mood::Indicative, Tense::Preterite) if model.preterite.is_some() => self.model_forms(model.preterite),
+            (_, Mood::Subjunctive, Tense::Present) if model.subjunctive.is_some() => self.model_forms(model.subjunctive),
+            (_, Mood::Subjunctive, Tense::Imperfect) if model.subjunctive_imperfect.is_some() => self.model_forms(model.subjunctive_imperfect),
+            (_, Mood::Imperative, _) if model.imperative.is_some() => self.model_forms(model.imperative),
+            ("french", Mood::Indicative, Tense::Imperfect) => {
+                // "nous faisons" gives "je faisais"
+                let stem = present[3].strip_suffix("ons")?;
+                Some(VerbGenerator::with_endings(stem, ["ais", "ais", "ait", "ions", "iez", "aient"]))
+            }
+            ("french", Mood::Indicative, Tense::Future) if self.future_stem().is_some() => {
+                Some(VerbGenerator::with_endings(&self.future_stem()?, ["ai", "as", "a", "ons", "ez", "ont"]))
+            }
+            ("french", Mood::Conditional, Tense::Present) if self.future_stem().is_some() => {
+                Some(VerbGenerator::with_endings(&self.future_stem()?, ["ais", "ais", "ait", "ions", "iez", "aient"]))
+            }
+            ("french", Mood::Subjunctive, Tense::Present) => {
+                // "ils viennent" gives "que je vienne", the imperfect gives "que nous venions"
+                let stem = present[5].strip_suffix("ent")?;
+                let imperfect = self.simple(Mood::Indicative, Tense::Imperfect)?;
+                let mut forms = VerbGenerator::with_endings(stem, ["e", "es", "e", "", "", "ent"]);
+                forms[3] = imperfect[3].clone();
+                forms[4] = imperfect[4].clone();
+                Some(forms)
+            }
+            ("french", Mood::Subjunctive, Tense::Imperfect) => {
+                // "tu vins" gives "que je vinsse" and "qu'il vînt"
+                let preterite = self.simple(Mood::Indicative, Tense::Preterite)?;
+                let stem = preterite[1].strip_suffix('s')?;
+                let mut forms = VerbGenerator::with_endings(stem, ["sse", "sses", "", "ssions", "ssiez", "ssent"]);
+                forms[2] = format!("{}t", circumflex_last_vowel(stem));
+                Some(forms)
+            }
+            ("french", Mood::Imperative, _) => {
+                let tu = match present[1].strip_suffix('s') {
+                    Some(tu) if present[1].ends_with("es") || present[1].ends_with("as") => tu.to_string(),
+                    _ => present[1].clone(),
+                };
+                Some([String::new(), tu, String::new(), present[3].clone(), present[4].clone(), String::new()])
+            }
+
+            ("spanish" | "italian", Mood::Indicative, Tense::Future) if self.future_stem().is_some() => {
+                let endings = match self.language.as_str() {
+                    "spanish" => ["é", "ás", "á", "emos", "éis", "án"],
+                    _ => ["ò", "ai", "à", "emo", "ete", "anno"],
+                };
+                Some(VerbGenerator::with_endings(&self.future_stem()?, endings))
+            }
+            ("spanish" | "italian", Mood::Conditional, Tense::Present) if self.future_stem().is_some() => {
+                let endings = match self.language.as_str() {
+                    "spanish" => ["ía", "ías", "ía", "íamos", "íais", "ían"],
+                    _ => ["ei", "esti", "ebbe", "emmo", "este", "ebbero"],
+                };
+                Some(VerbGenerator::with_endings(&self.future_stem()?, endings))
+            }
+            ("spanish", Mood::Subjunctive, Tense::Present) => {
+                // "tengo" gives "tenga"
+                let stem = present[0].strip_suffix('o')?;
+                let endings = match self.infinitive.ends_with("ar") {
+                    true => ["e", "es", "e", "emos", "éis", "en"],
+                    false => ["a", "as", "a", "amos", "áis", "an"],
+                };
+                Some(VerbGenerator::with_endings(stem, endings))
+            }
+            ("spanish", Mood::Subjunctive, Tense::Imperfect) => self.spanish_preterite_derived(["ra", "ras", "ra", "ramos", "rais", "ran"]),
+            ("spanish", Mood::Subjunctive, Tense::Future) => self.spanish_preterite_derived(["re", "res", "re", "remos", "reis", "ren"]),
+            ("spanish", Mood::Imperative, _) => {
+                let subjunctive = self.simple(Mood::Subjunctive, Tense::Present)?;
+                let vosotros = format!("{}d", self.infinitive.strip_suffix('r')?);
+                Some([
+                    String::new(),
+                    present[2].clone(),
+                    subjunctive[2].clone(),
+                    subjunctive[3].clone(),
+                    vosotros,
+                    subjunctive[5].clone(),
+                ])
+            }
+            ("italian", Mood::Subjunctive, Tense::Imperfect) => {
+                // "facevo" gives "facessi"
+                let imperfect = self.simple(Mood::Indicative, Tense::Imperfect)?;
+                let stem = imperfect[0].strip_suffix("vo")?;
+                Some(VerbGenerator::with_endings(stem, ["ssi", "ssi", "sse", "ssimo", "ste", "ssero"]))
+            }
+
+            _ => None,
+        };
+
+        // whatever the model leaves out follows the regular pattern
+        derived.or_else(|| self.regular_forms(mood, tense))
+    }
+
+    fn se_imperfect(&self) -> Option<[String; 6]> {
+        match self.language.as_str() {
+            "spanish" => self.spanish_preterite_derived(["se", "ses", "se", "semos", "seis", "sen"]),
+            _ => None,
+        }
+    }
+
+    /// The spanish imperfect and future subjunctive build on the third
+    /// person plural preterite: "tuvieron" gives "tuviera" and "tuviéramos".
+    fn spanish_preterite_derived(&self, endings: [&str; 6]) -> Option<[String; 6]> {
+        let preterite = self.simple(Mood::Indicative, Tense::Preterite)?;
+        let stem = preterite[5].strip_suffix("ron")?;
+
+        let mut forms = VerbGenerator::with_endings(stem, endings);
+        forms[3] = format!("{}{}", accent_last_vowel(stem), endings[3]);
+        Some(forms)
+    }
+}
+
+fn replace_last_vowel(stem: &str, replace: fn(char) -> Option<char>) -> String {
+    let mut chars = stem.chars().collect::<Vec<char>>();
+    if let Some(position) = chars.iter().rposition(|c| replace(*c).is_some()) {
+        chars[position] = replace(chars[position]).expect("vowel has a replacement");
+    }
+
+    chars.into_iter().collect()
+}
+
+fn accent_last_vowel(stem: &str) -> String {
+    replace_last_vowel(stem, |c| match c {
+        'a' => Some('á'),
+        'e' => Some('é'),
+        'i' => Some('í'),
+        'o' => Some('ó'),
+        'u' => Some('ú'),
+        _ => None,
+    })
+}
+
+fn circumflex_last_vowel(stem: &str) -> String {
+    replace_last_vowel(stem, |c| match c {
+        'a' => Some('â'),
+        'i' => Some('î'),
+        'u' => Some('û'),
+        _ => None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The forms of one generated table, joined with spaces.
+    fn table(language: &str, verb: &str, mood_label: &str, tense_label: &str) -> String {
+        let conjugations = generate_conjugations(language, verb).expect("verb conjugates offline");
+        let table = conjugations.conjugation_tables
+            .iter()
+            .find(|table| table.mood_label == mood_label && table.tense == tense_label)
+            .expect("table is generated");
+
+        table.forms
+            .iter()
+            .map(|form| form.form.clone())
+            .collect::<Vec<String>>()
+            .join(" ")
+    }
+
+    #[test]
+    fn regular_verbs_of_each_class() {
+        let expected = [
+            ("french", "parler", "indicatif", "présent", "parle parles parle parlons parlez parlent"),
+            ("french", "finir", "indicatif", "imparfait", "finissais finissais finissait finissions finissiez finissaient"),
+            ("french", "vendre", "indicatif", "futur simple", "vendrai vendras vendra vendrons vendrez vendront"),
+            ("spanish", "hablar", "indicativo", "pretérito", "hablé hablaste habló hablamos hablasteis hablaron"),
+            ("spanish", "comer", "subjuntivo", "presente", "coma comas coma comamos comáis coman"),
+            ("spanish", "vivir", "indicativo", "presente", "vivo vives vive vivimos vivís viven"),
+            ("italian", "parlare", "indicativo", "futuro semplice", "parlerò parlerai parlerà parleremo parlerete parleranno"),
+            ("italian", "temere", "indicativo", "imperfetto", "temevo temevi temeva temevamo temevate temevano"),
+            ("italian", "dormire", "indicativo", "presente", "dormo dormi dorme dormiamo dormite dormono"),
+        ];
+
+        for (language, verb, mood_label, tense_label, forms) in expected {
+            assert_eq!(table(language, verb, mood_label, tense_label), forms, "{verb} {mood_label} {tense_label}");
+        }
+    }
+
+    #[test]
+    fn compound_tenses_use_the_right_auxiliary() {
+        assert_eq!(
+            table("french", "parler", "indicatif", "passé composé"),
+            "ai parlé as parlé a parlé avons parlé avez parlé ont parlé",
+        );
+        assert_eq!(
+            table("italian", "partire", "indicativo", "passato prossimo"),
+            "sono partito/a sei partito/a è partito/a siamo partiti/e siete partiti/e sono partiti/e",
+        );
+        assert_eq!(
+            table("spanish", "vivir", "indicativo", "pretérito perfecto"),
+            "he vivido has vivido ha vivido hemos vivido habéis vivido han vivido",
+        );
+    }
+
+    #[test]
+    fn spelling_changes_carry_into_generated_tables() {
+        assert_eq!(table("french", "commencer", "indicatif", "présent").split(' ').nth(3), Some("commençons"));
+        assert_eq!(table("french", "manger", "indicatif", "imparfait").split(' ').next(), Some("mangeais"));
+        assert_eq!(table("spanish", "buscar", "indicativo", "pretérito").split(' ').next(), Some("busqué"));
+        assert_eq!(table("spanish", "pagar", "subjuntivo", "presente").split(' ').next(), Some("pague"));
+        assert_eq!(table("italian", "cercare", "indicativo", "presente").split(' ').nth(1), Some("cerchi"));
+        assert_eq!(table("italian", "mangiare", "indicativo", "futuro semplice").split(' ').next(), Some("mangerò"));
+    }
+
+    #[test]
+    fn irregular_models_and_their_derived_verbs() {
+        assert_eq!(table("french", "devenir", "indicatif", "présent"), "deviens deviens devient devenons devenez deviennent");
+        assert_eq!(table("spanish", "tener", "subjuntivo", "presente"), "tenga tengas tenga tengamos tengáis tengan");
+        assert_eq!(table("italian", "fare", "congiuntivo", "imperfetto"), "facessi facessi facesse facessimo faceste facessero");
+        assert_eq!(table("italian", "tuffare", "indicativo", "presente"), "tuffo tuffi tuffa tuffiamo tuffate tuffano");
+        assert_eq!(table("french", "répartir", "indicatif", "présent"), "répartis répartis répartit répartissons répartissez répartissent");
+    }
+
+    #[test]
+    fn je_elides_before_a_vowel() {
+        let conjugations = generate_conjugations("french", "aimer").expect("verb conjugates offline");
+        assert_eq!(conjugations.conjugation_tables[0].forms[0].pronoun, "j'");
+        assert!(conjugations.generated);
+    }
+}
@@ -0,0 +1,384 @@
+use serde::{Serialize, Deserialize};
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub enum Mood {
+    Indicative,
+    Subjunctive,
+    Conditional,
+    Imperative,
+    #[default]
+    Unknown,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub enum Tense {
+    Present,
+    Imperfect,
+    Preterite,
+    Future,
+    Perfect,
+    Pluperfect,
+    PastAnterior,
+    FuturePerfect,
+    #[default]
+    Unknown,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Person {
+    FirstSingular,
+    SecondSingular,
+    ThirdSingular,
+    FirstPlural,
+    SecondPlural,
+    ThirdPlural,
+}
+
+impl Mood {
+    pub fn parse(label: &str) -> Mood {
+        let label = fold(label);
+        match label.as_str() {
+            _ if label.contains("indic") => Mood::Indicative,
+            _ if label.contains("subj") => Mood::Subjunctive,
+            _ if label.contains("congiunt") => Mood::Subjunctive,
+            _ if label.contains("condi") => Mood::Conditional,
+            _ if label.contains("imperat") => Mood::Imperative,
+            _ => Mood::Unknown,
+        }
+    }
+}
+
+impl Tense {
+    /// Parses a tense label such as "passé composé" or "pretérito anterior".
+    /// Order matters: compound names are checked before the simple tense
+    /// names they contain.
+    pub fn parse(label: &str) -> Tense {
+        let label = fold(label);
+        let has = |keywords: &[&str]| keywords.iter().any(|k| label.contains(k));
+
+        if has(&["passe anterieur", "preterito anterior", "trapassato remoto"]) {
+            Tense::PastAnterior
+        } else if has(&["futur anterieur", "futuro perfecto", "futuro compuesto", "futuro anteriore", "future perfect"]) {
+            Tense::FuturePerfect
+        } else if has(&["plus-que-parfait", "pluscuamperfecto", "trapassato", "past perfect"]) {
+            Tense::Pluperfect
+        } else if has(&["passe simple", "passato remoto", "preterito indefinido", "perfecto simple"]) {
+            Tense::Preterite
+        } else if has(&["imparfait", "imperfecto", "imperfetto"]) {
+            Tense::Imperfect
+        } else if has(&["passe", "passato", "perfecto", "compose", "compuesto", "present perfect"]) {
+            Tense::Perfect
+        } else if has(&["preterito", "past"]) {
+            Tense::Preterite
+        } else if has(&["futur"]) {
+            Tense::Future
+        } else if has(&["present", "condicional", "conditionnel", "condizionale", "imperati"]) {
+            Tense::Present
+        } else {
+            Tense::Unknown
+        }
+    }
+
+    pub fn is_compound(&self) -> bool {
+        matches!(
+            self,
+            Tense::Perfect | Tense::Pluperfect | Tense::PastAnterior | Tense::FuturePerfect
+        )
+    }
+}
+
+impl Person {
+    pub const ALL: [Person; 6] = [
+        Person::FirstSingular,
+        Person::SecondSingular,
+        Person::ThirdSingular,
+        Person::FirstPlural,
+        Person::SecondPlural,
+        Person::ThirdPlural,
+    ];
+
+    /// Maps a pronoun cell ("je", "j'", "il, elle, on", "(tú)") to a person.
+    /// Ambiguous pronouns such as the english "you" return None.
+    pub fn parse(pronoun: &str) -> Option<Person> {
+        let pronoun = fold(pronoun);
+        let first = pronoun
+            .trim_matches(|c: char| c == '(' || c == ')' || c.is_whitespace())
+            .split([',', '/', ' '])
+            .next()
+            .unwrap_or("")
+            .trim_end_matches(['\'', '’', '.']);
+
+        match first {
+            "je" | "j" | "yo" | "io" | "i" => Some(Person::FirstSingular),
+            "tu" => Some(Person::SecondSingular),
+            "il" | "elle" | "on" | "el" | "ella" | "usted" | "ud"
+                | "lui" | "lei" | "egli" | "he" | "she" | "it" => Some(Person::ThirdSingular),
+            "nous" | "nosotros" | "nosotras" | "noi" | "we" => Some(Person::FirstPlural),
+            "vous" | "vosotros" | "vosotras" | "voi" => Some(Person::SecondPlural),
+            "ils" | "elles" | "ellos" | "ellas" | "ustedes" | "uds"
+                | "loro" | "essi" | "they" => Some(Person::ThirdPlural),
+            _ => None,
+        }
+    }
+
+    /// Falls back to the row position for the usual six row tables.
+    pub fn from_position(index: usize, num_rows: usize) -> Option<Person> {
+        match num_rows {
+            6 => Person::ALL.get(index).copied(),
+            _ => None,
+        }
+    }
+}
+
+/// Lowercases text and strips the accents used by the supported languages
+/// so labels can be compared regardless of how they were typed.
+pub fn fold(text: &str) -> String {
+    text.trim()
+        .to_lowercase()
+        .chars()
+        .map(|c| match c {
+            'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' => 'a',
+            'ç' => 'c',
+            'è' | 'é' | 'ê' | 'ë' => 'e',
+            'ì' | 'í' | 'î' | 'ï' => 'i',
+            'ñ' => 'n',
+            'ò' | 'ó' | 'ô' | 'õ' | 'ö' => 'o',
+            'ù' | 'ú' | 'û' | 'ü' => 'u',
+            'ý' | 'ÿ' => 'y',
+            _ => c,
+        })
+        .collect()
+}
+
+/// Abbreviations accepted for mood and tense names, per language.
+fn abbreviations(language: &str) -> &'static [(&'static str, &'static str)] {
+    match language {
+        "french" => &[
+            ("ind", "indicatif"),
+            ("subj", "subjonctif"),
+            ("cond", "conditionnel"),
+            ("imper", "impératif"),
+            ("pres", "présent"),
+            ("imp", "imparfait"),
+            ("impf", "imparfait"),
+            ("fut", "futur"),
+            ("pc", "passé composé"),
+            ("ps", "passé simple"),
+            ("pqp", "plus-que-parfait"),
+            ("pa", "passé antérieur"),
+            ("fa", "futur antérieur"),
+        ],
+        "spanish" => &[
+            ("ind", "indicativo"),
+            ("subj", "subjuntivo"),
+            ("cond", "condicional"),
+            ("imper", "imperativo"),
+            ("pres", "presente"),
+            ("imp", "imperfecto"),
+            ("impf", "imperfecto"),
+            ("pret", "pretérito"),
+            ("fut", "futuro"),
+            ("perf", "perfecto"),
+            ("pluscuam", "pluscuamperfecto"),
+        ],
+        "italian" => &[
+            ("ind", "indicativo"),
+            ("cong", "congiuntivo"),
+            ("subj", "congiuntivo"),
+            ("cond", "condizionale"),
+            ("imper", "imperativo"),
+            ("pres", "presente"),
+            ("imp", "imperfetto"),
+            ("impf", "imperfetto"),
+            ("fut", "futuro"),
+            ("pp", "passato prossimo"),
+            ("pr", "passato remoto"),
+            ("tp", "trapassato prossimo"),
+            ("tr", "trapassato remoto"),
+            ("fa", "futuro anteriore"),
+        ],
+        _ => &[],
+    }
+}
+
+/// Splits a tense query into folded words, expanding abbreviations.
+pub fn expand_tense_query(language: &str, query: &str) -> Vec<String> {
+    let abbreviations = abbreviations(language);
+
+    query
+        .split_whitespace()
+        .map(fold)
+        .flat_map(|word| {
+            let expanded = abbreviations
+                .iter()
+                .find(|(abbreviation, _)| *abbreviation == word)
+                .map(|(_, full)| fold(full))
+                .unwrap_or(word);
+
+            expanded
+                .split_whitespace()
+                .map(|x| x.to_string())
+                .collect::<Vec<String>>()
+        })
+        .collect()
+}
+
+/// Reflexive pronouns that belong to the verb typed after them.
+const REFLEXIVE_PRONOUNS: &[&str] = &["se", "s'", "s'en", "s'y"];
+
+/// Splits the arguments of a verb command into the verb, keeping reflexive
+/// pronouns as in "se lever" or "s'en aller", and the words naming a tense.
+pub fn split_verb_args(args: &[String]) -> (String, Vec<String>) {
+    let verb_len = args
+        .iter()
+        .position(|arg| !REFLEXIVE_PRONOUNS.contains(&fold(arg).replace('’', "'").as_str()))
+        .map_or(args.len(), |index| index + 1);
+
+    let (verb, tense) = args.split_at(verb_len);
+    (verb.join(" "), tense.to_vec())
+}
+
+/// Scores how well a typed word matches a label word: exact matches beat
+/// prefixes, which beat words one typo away.
+pub fn word_match_score(query_word: &str, label_word: &str) -> Option<u32> {
+    if query_word == label_word {
+        Some(3)
+    } else if query_word.len() >= 2 && label_word.starts_with(query_word) {
+        Some(2)
+    } else if query_word.chars().count() >= 4 && edit_distance(query_word, label_word) <= 1 {
+        Some(1)
+    } else {
+        None
+    }
+}
+
+/// Optimal string alignment distance, so swapped letters count as one typo.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a = a.chars().collect::<Vec<char>>();
+    let b = b.chars().collect::<Vec<char>>();
+
+    let mut distances = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in distances.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    distances[0] = (0..=b.len()).collect();
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let mut distance = (distances[i - 1][j] + 1)
+                .min(distances[i][j - 1] + 1)
+                .min(distances[i - 1][j - 1] + cost);
+
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                distance = distance.min(distances[i - 2][j - 2] + 1);
+            }
+            distances[i][j] = distance;
+        }
+    }
+
+    distances[a.len()][b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fold_strips_accents_and_case() {
+        assert_eq!(fold("  Passé Composé "), "passe compose");
+        assert_eq!(fold("Pretérito pluscuamperfecto"), "preterito pluscuamperfecto");
+        assert_eq!(fold("niño façade"), "nino facade");
+    }
+
+    #[test]
+    fn moods_parse_across_languages() {
+        assert_eq!(Mood::parse("Indicatif"), Mood::Indicative);
+        assert_eq!(Mood::parse("Subjuntivo"), Mood::Subjunctive);
+        assert_eq!(Mood::parse("Congiuntivo"), Mood::Subjunctive);
+        assert_eq!(Mood::parse("Conditionnel"), Mood::Conditional);
+        assert_eq!(Mood::parse("Impératif"), Mood::Imperative);
+        assert_eq!(Mood::parse("Participe"), Mood::Unknown);
+    }
+
+    #[test]
+    fn compound_tenses_win_over_the_simple_ones_they_contain() {
+        assert_eq!(Tense::parse("passé antérieur"), Tense::PastAnterior);
+        assert_eq!(Tense::parse("Pretérito anterior"), Tense::PastAnterior);
+        assert_eq!(Tense::parse("futur antérieur"), Tense::FuturePerfect);
+        assert_eq!(Tense::parse("futuro anteriore"), Tense::FuturePerfect);
+        assert_eq!(Tense::parse("plus-que-parfait"), Tense::Pluperfect);
+        assert_eq!(Tense::parse("trapassato prossimo"), Tense::Pluperfect);
+        assert_eq!(Tense::parse("passé composé"), Tense::Perfect);
+        assert_eq!(Tense::parse("passato prossimo"), Tense::Perfect);
+    }
+
+    #[test]
+    fn simple_tenses_parse_with_or_without_accents() {
+        assert_eq!(Tense::parse("présent"), Tense::Present);
+        assert_eq!(Tense::parse("present"), Tense::Present);
+        assert_eq!(Tense::parse("imparfait"), Tense::Imperfect);
+        assert_eq!(Tense::parse("passé simple"), Tense::Preterite);
+        assert_eq!(Tense::parse("pretérito indefinido"), Tense::Preterite);
+        assert_eq!(Tense::parse("preterito"), Tense::Preterite);
+        assert_eq!(Tense::parse("futur simple"), Tense::Future);
+        assert_eq!(Tense::parse("gérondif"), Tense::Unknown);
+    }
+
+    #[test]
+    fn pronouns_parse_to_persons() {
+        assert_eq!(Person::parse("j'"), Some(Person::FirstSingular));
+        assert_eq!(Person::parse("il, elle, on"), Some(Person::ThirdSingular));
+        assert_eq!(Person::parse("(tú)"), Some(Person::SecondSingular));
+        assert_eq!(Person::parse("él, ella, Ud."), Some(Person::ThirdSingular));
+        assert_eq!(Person::parse("loro"), Some(Person::ThirdPlural));
+        assert_eq!(Person::parse("you"), None);
+    }
+
+    #[test]
+    fn six_row_tables_fall_back_to_position() {
+        assert_eq!(Person::from_position(3, 6), Some(Person::FirstPlural));
+        assert_eq!(Person::from_position(3, 3), None);
+    }
+
+    #[test]
+    fn tense_queries_expand_abbreviations_and_fold_accents() {
+        assert_eq!(expand_tense_query("french", "subj pres"), ["subjonctif", "present"]);
+        assert_eq!(expand_tense_query("french", "pc"), ["passe", "compose"]);
+        assert_eq!(expand_tense_query("french", "Passé Composé"), ["passe", "compose"]);
+        assert_eq!(expand_tense_query("spanish", "pret perf"), ["preterito", "perfecto"]);
+        assert_eq!(expand_tense_query("italian", "cong impf"), ["congiuntivo", "imperfetto"]);
+        // abbreviations of one language mean nothing in another
+        assert_eq!(expand_tense_query("spanish", "pc"), ["pc"]);
+    }
+
+    #[test]
+    fn exact_words_beat_prefixes_which_beat_typos() {
+        assert_eq!(word_match_score("present", "present"), Some(3));
+        assert_eq!(word_match_score("pres", "present"), Some(2));
+        assert_eq!(word_match_score("imparfiat", "imparfait"), Some(1));
+        assert_eq!(word_match_score("p", "present"), None);
+        assert_eq!(word_match_score("futr", "passe"), None);
+    }
+
+    #[test]
+    fn swapped_letters_are_one_edit() {
+        assert_eq!(edit_distance("imparfait", "imparfait"), 0);
+        assert_eq!(edit_distance("imparfiat", "imparfait"), 1);
+        assert_eq!(edit_distance("futur", "futuro"), 1);
+        assert_eq!(edit_distance("passe", "pese"), 2);
+    }
+
+    fn words(text: &str) -> Vec<String> {
+        text.split_whitespace().map(|x| x.to_string()).collect()
+    }
+
+    #[test]
+    fn reflexive_pronouns_stay_with_the_verb() {
+        assert_eq!(split_verb_args(&words("parler")), ("parler".to_string(), vec![]));
+        assert_eq!(split_verb_args(&words("venir subj pres")), ("venir".to_string(), words("subj pres")));
+        assert_eq!(split_verb_args(&words("se lever pc")), ("se lever".to_string(), words("pc")));
+        assert_eq!(split_verb_args(&words("s'en aller")), ("s'en aller".to_string(), vec![]));
+        assert_eq!(split_verb_args(&words("s’y prendre fut")), ("s’y prendre".to_string(), words("fut")));
+    }
+}
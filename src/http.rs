@@ -0,0 +1,304 @@
+use std::{collections::HashMap, error::Error, fmt, io, sync::Arc, time::Duration};
+
+use reqwest::{
+    header::{HeaderMap, HeaderName, HeaderValue, CONTENT_TYPE, RETRY_AFTER},
+    Client,
+    Proxy,
+    StatusCode,
+};
+use encoding_rs::{Encoding, UTF_8};
+use tokio::{sync::Mutex, time::Instant};
+
+use crate::{
+    app_error::AppError,
+    status::{LoadStage, Progress},
+    wordreference::wordreference_utils,
+};
+
+const DEFAULT_USER_AGENT: &str = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 \
+    (KHTML, like Gecko) Chrome/56.0.2924.87 Safari/537.36";
+
+#[derive(Clone)]
+pub struct HttpOptions {
+    pub timeout: Duration, // for a whole request, body included
+    pub connect_timeout: Duration,
+    pub max_retries: u32, // of a request answered with 429 or 5xx
+    pub initial_backoff: Duration, // doubled on every retry
+    pub min_interval: Duration, // between two requests to the same host
+}
+
+impl Default for HttpOptions {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(15),
+            connect_timeout: Duration::from_secs(5),
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(500),
+            min_interval: Duration::from_millis(250),
+        }
+    }
+}
+
+/// Where and how requests are sent.
+#[derive(Clone, Default)]
+pub struct HttpConfig {
+    pub proxy: Option<String>, // e.g. "http://proxy:3128" or "socks5h://localhost:1080"
+    pub user_agent: Option<String>,
+    pub headers: Vec<(String, String)>, // sent with every request
+    pub base_url: Option<String>, // in place of wordreference, e.g. a local mock server
+    pub options: HttpOptions,
+}
+
+impl HttpConfig {
+    /// Overrides settings with the TRANSRUSTATE_PROXY, TRANSRUSTATE_USER_AGENT,
+    /// TRANSRUSTATE_HEADERS (one "Name: value" per line) and
+    /// TRANSRUSTATE_BASE_URL environment variables that are set.
+    pub fn with_env(self) -> HttpConfig {
+        let var = |name: &str| std::env::var(name).ok().filter(|value| !value.trim().is_empty());
+
+        let mut headers = self.headers;
+        if let Some(env_headers) = var("TRANSRUSTATE_HEADERS") {
+            // values can hold ';', as in cookies, but never a line break
+            for (name, value) in env_headers.lines().filter_map(|header| header.split_once(':')) {
+                let (name, value) = (name.trim().to_string(), value.trim().to_string());
+                headers.retain(|(other, _)| !other.eq_ignore_ascii_case(&name));
+                headers.push((name, value));
+            }
+        }
+
+        HttpConfig {
+            proxy: var("TRANSRUSTATE_PROXY").or(self.proxy),
+            user_agent: var("TRANSRUSTATE_USER_AGENT").or(self.user_agent),
+            headers,
+            base_url: var("TRANSRUSTATE_BASE_URL").or(self.base_url),
+            options: self.options,
+        }
+    }
+}
+
+/// Why a request failed.
+#[derive(Clone, Debug)]
+pub enum FetchError {
+    Timeout { host: String },
+    Status { host: String, status: u16, attempts: u32 },
+    ConnectionRefused { host: String },
+    Connection { host: String },
+    Other { message: String },
+}
+
+impl fmt::Display for FetchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FetchError::Timeout { host } => write!(f, "{host} took too long to answer, please try again"),
+            FetchError::Status { host, status, attempts: 1 } => write!(f, "{host} answered with HTTP {status}"),
+            FetchError::Status { host, status, attempts } => {
+                write!(f, "{host} answered with HTTP {status} after {attempts} attempts")
+            }
+            FetchError::ConnectionRefused { host } => write!(f, "The connection to {host} was refused"),
+            FetchError::Connection { host } => {
+                write!(f, "Could not connect to {host}, please check your network connection")
+            }
+            FetchError::Other { message } => write!(f, "The request failed: {message}"),
+        }
+    }
+}
+
+impl FetchError {
+    /// Whether the site couldn't be reached or was failing, rather than
+    /// answering that there was nothing to find.
+    pub fn is_unreachable(&self) -> bool {
+        match self {
+            FetchError::Timeout { .. } | FetchError::Connection { .. } | FetchError::ConnectionRefused { .. } => true,
+            FetchError::Status { status, .. } => *status >= 500,
+            FetchError::Other { .. } => false,
+        }
+    }
+
+    fn from_reqwest(err: reqwest::Error, host: &str) -> FetchError {
+        let host = host.to_string();
+        if err.is_timeout() {
+            return FetchError::Timeout { host };
+        }
+
+        // reqwest wraps the io error of a refused connection a few levels down
+        let mut source = err.source();
+        while let Some(cause) = source {
+            if let Some(io_error) = cause.downcast_ref::<io::Error>() {
+                match io_error.kind() {
+                    io::ErrorKind::ConnectionRefused => return FetchError::ConnectionRefused { host },
+                    io::ErrorKind::TimedOut => return FetchError::Timeout { host },
+                    _ => {}
+                }
+            }
+            source = cause.source();
+        }
+
+        match err.is_connect() {
+            true => FetchError::Connection { host },
+            false => FetchError::Other { message: err.to_string() },
+        }
+    }
+}
+
+/// A client retrying throttled and failed requests with exponential backoff,
+/// spacing out the requests it makes to each host.
+#[derive(Clone)]
+pub struct HttpClient {
+    client: Client,
+    options: HttpOptions,
+    base_url: String,
+    next_request: Arc<Mutex<HashMap<String, Instant>>>, // earliest time per host
+}
+
+impl HttpClient {
+    pub fn new(config: &HttpConfig) -> Result<HttpClient, AppError> {
+        let config_error = |setting: &str, err: String| AppError::Config {
+            message: format!("Invalid HTTP {setting}: {err}"),
+        };
+
+        let mut headers = HeaderMap::new();
+        for (name, value) in &config.headers {
+            let name = HeaderName::from_bytes(name.as_bytes())
+                .map_err(|err| config_error("header name", format!("'{name}', {err}")))?;
+            let value = HeaderValue::from_str(value)
+                .map_err(|err| config_error("header value", format!("'{value}', {err}")))?;
+            headers.insert(name, value);
+        }
+
+        let mut client = Client::builder()
+            .user_agent(config.user_agent.as_deref().unwrap_or(DEFAULT_USER_AGENT))
+            .default_headers(headers)
+            .timeout(config.options.timeout)
+            .connect_timeout(config.options.connect_timeout);
+
+        if let Some(proxy) = &config.proxy {
+            let proxy = Proxy::all(proxy.as_str())
+                .map_err(|err| config_error("proxy", format!("'{proxy}', {err}")))?;
+            client = client.proxy(proxy);
+        }
+
+        let base_url = config.base_url
+            .as_deref()
+            .unwrap_or(wordreference_utils::BASE_URL)
+            .trim_end_matches('/')
+            .to_string();
+        reqwest::Url::parse(&base_url)
+            .map_err(|err| config_error("base URL", format!("'{base_url}', {err}")))?;
+
+        Ok(HttpClient {
+            client: client.build().map_err(|err| config_error("client", err.to_string()))?,
+            options: config.options.clone(),
+            base_url,
+            next_request: Arc::new(Mutex::new(HashMap::new())),
+        })
+    }
+
+    /// Where wordreference pages are requested from.
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Waits for the host's turn, reserving the next one.
+    async fn wait_for_turn(&self, host: &str) {
+        let mut next_request = self.next_request.lock().await;
+        let now = Instant::now();
+        let turn = next_request
+            .get(host)
+            .copied()
+            .filter(|turn| *turn > now)
+            .unwrap_or(now);
+        next_request.insert(host.to_string(), turn + self.options.min_interval);
+        drop(next_request);
+
+        tokio::time::sleep_until(turn).await;
+    }
+
+    /// The body of a page, reporting the connection and how much of it
+    /// has arrived.
+    pub async fn get_text(&self, url: &str, progress: &Progress) -> Result<String, FetchError> {
+        let host = reqwest::Url::parse(url)
+            .ok()
+            .and_then(|url| url.host_str().map(|host| host.to_string()))
+            .unwrap_or_else(|| url.to_string());
+
+        let mut backoff = self.options.initial_backoff;
+        let mut attempts = 0;
+        loop {
+            attempts += 1;
+            self.wait_for_turn(&host).await;
+
+            progress(LoadStage::Connecting { host: host.clone() });
+            let mut response = self.client
+                .get(url)
+                .send()
+                .await
+                .map_err(|err| FetchError::from_reqwest(err, &host))?;
+
+            let status = response.status();
+            let retryable = status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error();
+            if retryable && attempts <= self.options.max_retries {
+                // a server asking to wait gets its way, if not unreasonably long
+                let retry_after = response.headers()
+                    .get(RETRY_AFTER)
+                    .and_then(|value| value.to_str().ok())
+                    .and_then(|value| value.parse::<u64>().ok())
+                    .map(Duration::from_secs)
+                    .filter(|delay| *delay <= Duration::from_secs(60));
+
+                tokio::time::sleep(retry_after.unwrap_or(backoff)).await;
+                backoff *= 2;
+                continue;
+            }
+
+            if !status.is_success() {
+                return Err(FetchError::Status {
+                    host,
+                    status: status.as_u16(),
+                    attempts,
+                });
+            }
+
+            let encoding = charset(response.headers());
+            let mut body = Vec::new();
+            while let Some(chunk) = response.chunk().await.map_err(|err| FetchError::from_reqwest(err, &host))? {
+                body.extend_from_slice(&chunk);
+                progress(LoadStage::Downloading { host: host.clone(), bytes: body.len() });
+            }
+
+            let (text, _, _) = encoding.decode(&body);
+            return Ok(text.into_owned());
+        }
+    }
+}
+
+/// The encoding named by a response's Content-Type, UTF-8 when it names none
+/// or one unknown, as `Response::text` would decode it.
+fn charset(headers: &HeaderMap) -> &'static Encoding {
+    headers
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|content_type| {
+            content_type
+                .split(';')
+                .skip(1)
+                .filter_map(|parameter| parameter.split_once('='))
+                .find(|(name, _)| name.trim().eq_ignore_ascii_case("charset"))
+                .and_then(|(_, label)| Encoding::for_label(label.trim().trim_matches('"').as_bytes()))
+        })
+        .unwrap_or(UTF_8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_failures_to_reach_the_site_count_as_unreachable() {
+        let host = "www.wordreference.com".to_string();
+        assert!(FetchError::Timeout { host: host.clone() }.is_unreachable());
+        assert!(FetchError::ConnectionRefused { host: host.clone() }.is_unreachable());
+        assert!(FetchError::Status { host: host.clone(), status: 503, attempts: 3 }.is_unreachable());
+        assert!(!FetchError::Status { host, status: 404, attempts: 1 }.is_unreachable());
+        assert!(!FetchError::Other { message: "bad url".to_string() }.is_unreachable());
+    }
+}
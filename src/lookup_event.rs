@@ -1,10 +1,30 @@
-use std::sync::Arc;
-use reqwest::Client;
+use std::{
+    collections::HashMap,
+    hash::Hash,
+    path::Path,
+    sync::{atomic::{AtomicBool, Ordering}, Arc, MutexGuard},
+};
+use futures::future::{BoxFuture, FutureExt, Shared};
+use tokio::sync::mpsc::Sender;
 
 use crate::{
     app::App,
+    app_error::AppError,
+    app_event::AppEvent,
+    classification::{self, Classification},
+    comparison::{ComparedVerb, Comparison},
+    conjugator,
+    deck::{self, Card, Review},
+    export::{self, ExportFormat, Note, NoteKind},
+    wordreference::wordreference_utils,
     conjugations::VerbConjugations,
+    generator,
+    grammar,
+    http::HttpClient,
     user_error::UserError,
+    variants::Variant,
+    quiz::{Quiz, QuizOptions},
+    status::{LoadStage, Progress},
     definitions::WordDefinitions
 };
 
@@ -12,59 +32,261 @@ use rusqlite::{Connection, Result};
 
 pub enum LookupEvent {
     Verb,
+    Compare,
+    Like,
+    Quiz,
+    Deck,
+    Review,
+    Grade { card: Card, grade: u8 },
+    Export,
     Definition,
     Translation,
 }
 
+impl LookupEvent {
+    /// Grades and deck changes only write to the database, and cancelling
+    /// lookups leaves them to finish.
+    pub fn is_write(&self) -> bool {
+        matches!(self, LookupEvent::Grade { .. } | LookupEvent::Deck)
+    }
+}
+
+/// The command a lookup was dispatched for, as typed at the time.
+pub struct LookupRequest {
+    pub input: String,
+    pub language: String,
+    pub native_language: String, // what def translates from and trans to
+    pub generation: u64, // the app's lookup generation when dispatched
+}
+
+impl LookupRequest {
+    /// The words following the command's name.
+    fn args(&self) -> Vec<String> {
+        self.input
+            .split_whitespace()
+            .skip(1)
+            .map(|x| x.to_string())
+            .collect()
+    }
+
+    /// Everything following the command's name, spacing kept.
+    fn body(&self) -> String {
+        self.input
+            .split(" ")
+            .skip(1)
+            .collect::<Vec<&str>>()
+            .join(" ")
+    }
+}
+
+tokio::task_local! {
+    /// The generation of the lookup a task runs, unset for writes.
+    static GENERATION: u64;
+}
+
+/// Whether a lookup of the given generation still has a say, which writes,
+/// having none, always do.
+fn is_current(generation: Option<u64>, app: &App) -> bool {
+    generation.map_or(true, |generation| generation == app.lookup_generation())
+}
+
+/// The lookups awaiting a fetch, which all see the stages it goes through.
+#[derive(Default)]
+struct Watchers {
+    latest: Option<LoadStage>, // for lookups joining late
+    progress: Vec<Progress>,
+}
+
+struct Fetch<T, E> {
+    result: Shared<BoxFuture<'static, Result<T, E>>>,
+    watchers: Arc<std::sync::Mutex<Watchers>>,
+}
+
+type InFlight<K, T, E> = std::sync::Mutex<HashMap<K, Fetch<T, E>>>;
+
+/// Awaits the fetch in flight for a key, starting it if there is none. The
+/// fetch reports its stages to the progress of every lookup awaiting it.
+async fn coalesce<K, T, E>(
+    in_flight: &InFlight<K, T, E>,
+    key: K,
+    progress: Progress,
+    fetch: impl FnOnce(Progress) -> BoxFuture<'static, Result<T, E>>,
+) -> Result<T, E>
+where
+    K: Eq + Hash + Clone,
+    T: Clone,
+    E: Clone,
+{
+    let shared = {
+        let mut in_flight = in_flight.lock().expect("In-flight fetches lock");
+        let fetch = in_flight.entry(key.clone()).or_insert_with(|| {
+            let watchers = Arc::new(std::sync::Mutex::new(Watchers::default()));
+            let broadcast: Progress = {
+                let watchers = Arc::clone(&watchers);
+                Arc::new(move |stage: LoadStage| {
+                    let mut watchers = watchers.lock().expect("Fetch watchers lock");
+                    for progress in &watchers.progress {
+                        progress(stage.clone());
+                    }
+                    watchers.latest = Some(stage);
+                })
+            };
+            Fetch {
+                result: fetch(broadcast).shared(),
+                watchers,
+            }
+        });
+
+        let mut watchers = fetch.watchers.lock().expect("Fetch watchers lock");
+        if let Some(stage) = watchers.latest.clone() {
+            progress(stage);
+        }
+        watchers.progress.push(progress);
+        fetch.result.clone()
+    };
+
+    let result = shared.clone().await;
+
+    // a newer fetch may have replaced this one meanwhile, and is left in place
+    let mut in_flight = in_flight.lock().expect("In-flight fetches lock");
+    if in_flight.get(&key).is_some_and(|current| current.result.ptr_eq(&shared)) {
+        in_flight.remove(&key);
+    }
+    result
+}
+
 pub struct LookupEventHandler {
-    app: Arc<tokio::sync::Mutex<App>>,
-    client: Client,
-    connection: Connection,
+    app_tx: Sender<AppEvent>, // results go back to the app as updates
+    client: HttpClient,
+    // lookups run as concurrent tasks, each locking the connection briefly
+    connection: std::sync::Mutex<Connection>,
+    deck_auto: AtomicBool, // add every successful lookup to the deck
+    last_card: std::sync::Mutex<Option<Card>>, // the latest lookup, for `deck add`
+
+    // fetches in progress, shared by identical lookups made meanwhile
+    conjugation_fetches: InFlight<(String, String), VerbConjugations, AppError>,
+    definition_fetches: InFlight<(String, String, String), WordDefinitions, AppError>,
 }
 
 impl LookupEventHandler {
-    pub fn new(app: Arc<tokio::sync::Mutex<App>>) -> Self {
-        let app_user_agent = "user-agent': 'Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/56.0.2924.87 Safari/537.36".to_string();
-        let client = reqwest::Client::builder()
-            .user_agent(app_user_agent)
-            .build()
-            .expect("Create a client");
-
-        let connection = LookupEventHandler::init_db();
+    pub fn new(app_tx: Sender<AppEvent>, client: HttpClient, connection: Connection) -> Self {
 
         Self {
-            app,
+            app_tx,
             client,
-            connection,
+            connection: std::sync::Mutex::new(connection),
+            deck_auto: AtomicBool::new(true),
+            last_card: std::sync::Mutex::new(None),
+            conjugation_fetches: std::sync::Mutex::new(HashMap::new()),
+            definition_fetches: std::sync::Mutex::new(HashMap::new()),
         }
     }
 
-    pub async fn handle_lookup_event(&mut self, lookup_event: LookupEvent) {
-        let mut app = self.app.lock().await;
-        app.start_load();
-        drop(app);
+    /// Runs a lookup, ending the load the app started when dispatching it
+    /// unless it was a write.
+    pub async fn handle_lookup_event(&self, lookup_event: LookupEvent, request: LookupRequest) {
+        // writes aren't cancelled, so their updates always apply
+        match lookup_event.is_write() {
+            true => self.run_lookup_event(lookup_event, request).await,
+            false => {
+                let generation = request.generation;
+                GENERATION.scope(generation, self.run_lookup_event(lookup_event, request)).await;
+            }
+        }
+    }
 
+    async fn run_lookup_event(&self, lookup_event: LookupEvent, request: LookupRequest) {
+        let is_write = lookup_event.is_write();
         match lookup_event {
             LookupEvent::Verb => {
-                self.handle_verb_lookup().await;
+                self.handle_verb_lookup(request).await;
+            },
+            LookupEvent::Compare => {
+                self.handle_verb_comparison(request).await;
+            },
+            LookupEvent::Like => {
+                self.handle_model_lookup(request).await;
+            },
+            LookupEvent::Quiz => {
+                self.handle_quiz_start(request).await;
+            },
+            LookupEvent::Deck => {
+                self.handle_deck_command(request).await;
+            },
+            LookupEvent::Review => {
+                self.handle_review_start(request).await;
+            },
+            LookupEvent::Grade { card, grade } => {
+                self.handle_review_grade(card, grade).await;
+            },
+            LookupEvent::Export => {
+                self.handle_export(request).await;
             },
             LookupEvent::Definition => {
-                self.handle_word_definition().await;
+                self.handle_word_definition(request).await;
             },
             LookupEvent::Translation => {
-                self.handle_word_translation().await;
+                self.handle_word_translation(request).await;
+            }
+        };
+        if !is_write {
+            self.update(|app| app.end_load()).await;
+        }
+    }
+
+    /// Hands a change to the app, which applies it between other events.
+    /// Nothing receives it in batch runs, where it is dropped. Changes still
+    /// queued when their lookup is cancelled are dropped too.
+    async fn update(&self, update: impl FnOnce(&mut App) + Send + 'static) {
+        let generation = GENERATION.try_with(|generation| *generation).ok();
+        let update = move |app: &mut App| {
+            if is_current(generation, app) {
+                update(app);
             }
         };
-        let mut app = self.app.lock().await;
-        app.end_load();
+        let _ = self.app_tx.send(AppEvent::Update(Box::new(update))).await;
+    }
+
+    /// Shows the stages of a lookup in the status bar as they happen,
+    /// skipping any the app is too busy to take or the lookup was cancelled.
+    fn progress(&self) -> Progress {
+        let app_tx = self.app_tx.clone();
+        let generation = GENERATION.try_with(|generation| *generation).ok();
+        Arc::new(move |stage: LoadStage| {
+            let report = move |app: &mut App| {
+                if is_current(generation, app) {
+                    app.report_stage(stage);
+                }
+            };
+            let _ = app_tx.try_send(AppEvent::Update(Box::new(report)));
+        })
+    }
+
+    /// Shows an error, which the status bar reports as the lookup failing.
+    async fn show_error(&self, error: UserError) {
+        self.update(move |app| {
+            app.report_stage(LoadStage::Failed);
+            app.set_error(error);
+        }).await;
     }
 
-    fn init_db() -> Connection {
-        let mut db_path = home::home_dir().expect("Open home directory.");
-        db_path.push(".lang_rs.db");
+    /// Opens the cache, creating its tables the first time. A path of
+    /// ":memory:" gives a throwaway database.
+    pub fn init_db(db_path: &Path) -> Result<Connection, AppError> {
+        let setup_error = |err: rusqlite::Error| AppError::Cache {
+            message: format!("could not open {}, {err}", db_path.display()),
+        };
+
+        if let Some(parent) = db_path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent).map_err(|err| AppError::Cache {
+                message: format!("could not create {}, {err}", parent.display()),
+            })?;
+        }
+        let connection = Connection::open(db_path).map_err(setup_error)?;
 
-        let connection = Connection::open(db_path)
-            .expect("Connected to the sqlite database");
+        // a batch run and the app can write at the same time
+        connection.busy_timeout(std::time::Duration::from_secs(10))
+            .map_err(setup_error)?;
 
         connection.execute(
             "CREATE TABLE IF NOT EXISTS rootwords (
@@ -74,7 +296,7 @@ impl LookupEventHandler {
                 rootword TEXT NOT NULL
             )",
             [],
-        ).expect("Initialized conjugations table");
+        ).map_err(setup_error)?;
 
         connection.execute(
             "CREATE TABLE IF NOT EXISTS conjugations (
@@ -84,7 +306,7 @@ impl LookupEventHandler {
                 verb_conjugations TEXT NOT NULL
             )",
            [],
-        ).expect("Initialized conjugations table");
+        ).map_err(setup_error)?;
 
         connection.execute(
             "CREATE TABLE IF NOT EXISTS definitions (
@@ -95,9 +317,39 @@ impl LookupEventHandler {
                 word_definitions TEXT NOT NULL
             )",
            [],
-        ).expect("Initialized conjugations table");
+        ).map_err(setup_error)?;
+
+        connection.execute(
+            "CREATE TABLE IF NOT EXISTS cards (
+                id INTEGER PRIMARY KEY,
+                language TEXT NOT NULL,
+                kind TEXT NOT NULL,
+                front TEXT NOT NULL,
+                back TEXT NOT NULL,
+                ease REAL NOT NULL,
+                interval INTEGER NOT NULL,
+                repetitions INTEGER NOT NULL,
+                due INTEGER NOT NULL,
+                UNIQUE (language, kind, front)
+            )",
+           [],
+        ).map_err(setup_error)?;
+
+        connection.execute(
+            "CREATE TABLE IF NOT EXISTS reviews (
+                id INTEGER PRIMARY KEY,
+                card_id INTEGER NOT NULL,
+                grade INTEGER NOT NULL,
+                reviewed_at INTEGER NOT NULL
+            )",
+           [],
+        ).map_err(setup_error)?;
 
-        connection
+        Ok(connection)
+    }
+
+    fn connection(&self) -> MutexGuard<'_, Connection> {
+        self.connection.lock().expect("Database connection lock")
     }
 
     fn map_word_to_root(
@@ -105,11 +357,11 @@ impl LookupEventHandler {
         word: String,
         language: String,
     ) -> Result<String> {
-        self.connection.query_row(
+        self.connection().query_row(
             "SELECT rootword \
             FROM rootwords \
             WHERE language = ?1 AND word = ?2",
-            &[&language.to_string(), &word.to_string()],
+            [&language.to_string(), &word.to_string()],
             |row| row.get(0),
         )
     }
@@ -119,11 +371,11 @@ impl LookupEventHandler {
         verb: String,
         language: String,
     ) -> Result<String> {
-        self.connection.query_row(
+        self.connection().query_row(
             "SELECT verb_conjugations \
             FROM conjugations \
             WHERE language = ?1 AND verb = ?2",
-            &[&language.to_string(), &verb.to_string()],
+            [&language.to_string(), &verb.to_string()],
             |row| row.get(0),
         )
     }
@@ -134,71 +386,492 @@ impl LookupEventHandler {
         to_language: String,
         from_language: String,
     ) -> Result<String> {
-        self.connection.query_row(
+        self.connection().query_row(
             "SELECT word_definitions \
             FROM definitions \
             WHERE word = ?1 AND to_language = ?2 AND from_language = ?3",
-            &[&word.to_string(), &to_language.to_string(), &from_language.to_string()],
+            [&word.to_string(), &to_language.to_string(), &from_language.to_string()],
             |row| row.get(0),
         )
     }
 
-    async fn handle_verb_lookup(&mut self) {
-        match self.attempt_verb_lookup().await {
+    async fn handle_verb_lookup(&self, request: LookupRequest) {
+        let (flags, args): (Vec<String>, Vec<String>) = request.args()
+            .into_iter()
+            .partition(|arg| arg.starts_with('-'));
+        let language = request.language;
+
+        let (verb, tense_words) = grammar::split_verb_args(&args);
+        let tense_query = tense_words.join(" ");
+        let show_all_tenses = flags.iter().any(|flag| flag == "-a");
+        let negative = flags.iter().any(|flag| flag == "-n");
+        let interrogative = flags.iter().any(|flag| flag == "-i");
+
+        match self.attempt_verb_lookup(verb, language.clone(), true).await {
             Err(err) => {
-                let mut app = self.app.lock().await;
-                app.set_error(err);
+                self.show_error(err.into()).await;
             }
             Ok(conjugations) => {
-                let mut app_obj = self.app.lock().await;
-                app_obj.set_conjugations(conjugations);
+                // conjugations made up offline are never worth learning from
+                let card = Card::from_conjugations(&conjugations, &language)
+                    .filter(|_| !conjugations.generated);
+                self.offer_card(card);
+
+                self.update(move |app_obj| {
+                    app_obj.set_conjugations(conjugations);
+
+                    if show_all_tenses {
+                        app_obj.set_all_tenses(true);
+                    }
+
+                    if negative || interrogative {
+                        app_obj.set_variant(Variant {
+                            negative,
+                            interrogative,
+                        });
+                    }
+
+                    if !tense_query.is_empty() {
+                        if let Err(err) = app_obj.open_tense(&tense_query) {
+                            app_obj.set_error(err);
+                        }
+                    }
+                }).await;
             }
         };
     }
 
-    async fn load_translation_table(&mut self, to_language: String, from_language: String) {
+    async fn handle_verb_comparison(&self, request: LookupRequest) {
+        let args = request.args();
+        let default_language = request.language;
+
+        if args.len() != 2 {
+            self.show_error(UserError {
+                message: "Usage: compare <verb> <verb>, e.g. 'compare être avoir' \
+                or 'compare es:venir it:venire'".to_string(),
+            }).await;
+            return;
+        }
+
+        let mut compared_verbs: Vec<ComparedVerb> = Vec::new();
+        for arg in args {
+            // a verb can be prefixed with its language, e.g. "es:venir"
+            let (language, verb) = match arg.split_once(':') {
+                Some((language, verb)) => match wordreference_utils::parse_language(language) {
+                    Some(language) => (language, verb.to_string()),
+                    None => {
+                        self.show_error(UserError {
+                            message: format!("Unsupported language '{language}' in '{arg}'"),
+                        }).await;
+                        return;
+                    }
+                },
+                None => (default_language.clone(), arg),
+            };
+
+            match self.attempt_verb_lookup(verb, language.clone(), true).await {
+                Ok(conjugations) => compared_verbs.push(ComparedVerb {
+                    conjugations,
+                    language,
+                }),
+                Err(err) => {
+                    self.show_error(err.into()).await;
+                    return;
+                }
+            }
+        }
+
+        let right = compared_verbs.pop().expect("compared two verbs");
+        let left = compared_verbs.pop().expect("compared two verbs");
+        let comparison = Comparison::new(left, right);
+
+        if comparison.pairs.is_empty() {
+            self.show_error(UserError {
+                message: "These verbs have no tenses in common to compare".to_string(),
+            }).await;
+        } else {
+            self.update(move |app| app.set_comparison(comparison)).await;
+        }
+    }
+
+    async fn handle_model_lookup(&self, request: LookupRequest) {
+        let args = request.args();
+        let language = request.language;
+
+        let [model] = args.as_slice() else {
+            self.show_error(UserError {
+                message: "Usage: like <verb>, e.g. 'like venir'".to_string(),
+            }).await;
+            return;
+        };
+        let model = conjugator::base_infinitive(&language, model);
+
+        match self.cached_verbs_like(&model, &language) {
+            Ok(verbs) if !verbs.is_empty() => self.update(move |app| app.set_model_verbs(&model, verbs)).await,
+            _ => self.show_error(UserError {
+                message: format!(
+                    "No cached {language} verbs conjugate like '{model}', \
+                    look some up with conj first",
+                ),
+            }).await,
+        }
+    }
+
+    async fn handle_quiz_start(&self, request: LookupRequest) {
+        let quiz = QuizOptions::parse(request.args(), &request.language).and_then(|options| {
+            let verbs = self.cached_conjugations(&options.language).unwrap_or_default();
+            Quiz::new(options, verbs)
+        });
+
+        match quiz {
+            Ok(quiz) => self.update(move |app| app.start_quiz(quiz)).await,
+            Err(err) => self.show_error(err).await,
+        }
+    }
+
+    async fn handle_deck_command(&self, request: LookupRequest) {
+        let args = request.args();
+        let language = request.language;
+
+        let args = args.iter().map(|arg| arg.as_str()).collect::<Vec<&str>>();
+        let last_card = self.last_card.lock().expect("Last card lock").clone();
+        let note = match args.as_slice() {
+            [] => String::new(),
+            ["add"] => match last_card {
+                Some(card) => match self.insert_card(&card) {
+                    Ok(0) => format!("'{}' is already in the deck", card.front),
+                    Ok(_) => format!("added '{}'", card.front),
+                    Err(_err) => {
+                        self.show_error(UserError {
+                            message: "Could not add the card to the deck".to_string(),
+                        }).await;
+                        return;
+                    }
+                },
+                None => {
+                    self.show_error(UserError {
+                        message: "Nothing to add, look up a word with def, trans or conj first".to_string(),
+                    }).await;
+                    return;
+                }
+            },
+            ["auto", "on"] => {
+                self.deck_auto.store(true, Ordering::Relaxed);
+                "lookups are added automatically".to_string()
+            }
+            ["auto", "off"] => {
+                self.deck_auto.store(false, Ordering::Relaxed);
+                "lookups are only added with deck add".to_string()
+            }
+            _ => {
+                self.show_error(UserError {
+                    message: "Usage: deck [add | auto on | auto off]".to_string(),
+                }).await;
+                return;
+            }
+        };
+
+        match self.cards(&language, None) {
+            Ok(cards) => self.update(move |app| app.set_deck(cards, &note)).await,
+            Err(_err) => self.show_error(UserError {
+                message: "Could not read the deck".to_string(),
+            }).await,
+        }
+    }
+
+    async fn handle_review_start(&self, request: LookupRequest) {
+        let language = request.language;
+
+        let cards = self.cards(&language, Some(deck::now())).unwrap_or_default();
+        match cards.is_empty() {
+            true => self.show_error(UserError {
+                message: format!("No {language} cards are due for review"),
+            }).await,
+            false => self.update(move |app| app.start_review(Review::new(cards))).await,
+        }
+    }
+
+    async fn handle_review_grade(&self, card: Card, grade: u8) {
+        if self.update_card(&card, grade).is_err() {
+            self.show_error(UserError {
+                message: format!("Could not save the review of '{}'", card.front),
+            }).await;
+        }
+    }
+
+    async fn handle_export(&self, request: LookupRequest) {
+        let args = request.args();
+        let language = request.language;
+
+        let (format, path) = match args.as_slice() {
+            [format, path] => match ExportFormat::parse(format) {
+                Some(format) => (format, path.clone()),
+                None => {
+                    self.show_error(UserError {
+                        message: format!("Unknown export format '{format}', try anki, csv or tsv"),
+                    }).await;
+                    return;
+                }
+            },
+            _ => {
+                self.show_error(UserError {
+                    message: "Usage: export anki|csv|tsv <file>, e.g. 'export anki french.apkg'".to_string(),
+                }).await;
+                return;
+            }
+        };
+
+        let mut notes = self.cached_definitions(&language)
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|definitions| Note::from_definitions(definitions, &language))
+            .collect::<Vec<Note>>();
+        for conjugations in self.cached_conjugations(&language).unwrap_or_default() {
+            notes.extend(Note::from_conjugations(&conjugations, &language));
+        }
+
+        if notes.is_empty() {
+            self.show_error(UserError {
+                message: format!("Nothing cached in {language} to export, look some words up first"),
+            }).await;
+            return;
+        }
+
+        let exported = export::export(
+            &notes,
+            format,
+            std::path::Path::new(&path),
+            &format!("transrustate::{language}"),
+        );
+        match exported {
+            Ok(()) => {
+                let num_translations = notes
+                    .iter()
+                    .filter(|note| note.kind == NoteKind::Translation)
+                    .count();
+                let num_conjugations = notes.len() - num_translations;
+                self.update(move |app| app.set_export_summary(&path, num_translations, num_conjugations)).await;
+            }
+            Err(err) => self.show_error(err).await,
+        }
+    }
+
+    /// Runs a word through the cache-then-fetch lookup of a `conj`, `def`
+    /// or `trans` command without showing it, returning what it was cached as.
+    pub async fn warm_cache(
+        &self,
+        kind: &str,
+        word: &str,
+        language: &str,
+        native_language: &str,
+    ) -> Result<String, AppError> {
+        match kind {
+            // offline conjugations are never cached, so they don't count
+            "conj" => self.attempt_verb_lookup(word.to_string(), language.to_string(), false)
+                .await
+                .map(|conjugations| conjugations.verb),
+            // the same argument order as load_translation_table's
+            "def" => self.attempt_word_definition(word.to_string(), native_language.to_string(), language.to_string())
+                .await
+                .map(|definitions| definitions.word),
+            "trans" => self.attempt_word_definition(word.to_string(), language.to_string(), native_language.to_string())
+                .await
+                .map(|definitions| definitions.word),
+            _ => Err(AppError::Config {
+                message: format!("Unknown lookup kind '{kind}', try conj, def or trans"),
+            }),
+        }
+    }
+
+    /// Remembers a lookup's card for `deck add`, adding it right away in auto mode.
+    fn offer_card(&self, card: Option<Card>) {
+        if let (Some(card), true) = (&card, self.deck_auto.load(Ordering::Relaxed)) {
+            // failing to add a card shouldn't fail the lookup
+            let _ = self.insert_card(card);
+        }
+        *self.last_card.lock().expect("Last card lock") = card;
+    }
+
+    /// Adds a card unless the deck already has one for the same word,
+    /// returning how many were added.
+    fn insert_card(&self, card: &Card) -> Result<usize> {
+        self.connection().execute(
+            "INSERT OR IGNORE INTO cards \
+            (language, kind, front, back, ease, interval, repetitions, due) \
+            values (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            rusqlite::params![
+                card.language,
+                card.kind,
+                card.front,
+                card.back,
+                card.ease,
+                card.interval,
+                card.repetitions,
+                card.due,
+            ],
+        )
+    }
+
+    fn update_card(&self, card: &Card, grade: u8) -> Result<()> {
+        // a card is never rescheduled without its review being logged
+        let connection = self.connection();
+        let transaction = connection.unchecked_transaction()?;
+
+        transaction.execute(
+            "UPDATE cards \
+            SET ease = ?1, interval = ?2, repetitions = ?3, due = ?4 \
+            WHERE id = ?5",
+            rusqlite::params![card.ease, card.interval, card.repetitions, card.due, card.id],
+        )?;
+
+        transaction.execute(
+            "INSERT INTO reviews \
+            (card_id, grade, reviewed_at) \
+            values (?1, ?2, ?3)",
+            rusqlite::params![card.id, grade, deck::now()],
+        )?;
+
+        transaction.commit()
+    }
+
+    /// The cards of a language by due date, only those due by a time if given.
+    fn cards(&self, language: &str, due_by: Option<i64>) -> Result<Vec<Card>> {
+        let connection = self.connection();
+        let mut statement = connection.prepare(
+            "SELECT id, language, kind, front, back, ease, interval, repetitions, due \
+            FROM cards \
+            WHERE language = ?1 AND due <= ?2 \
+            ORDER BY due",
+        )?;
+
+        let cards = statement
+            .query_map(rusqlite::params![language, due_by.unwrap_or(i64::MAX)], |row| {
+                Ok(Card {
+                    id: row.get(0)?,
+                    language: row.get(1)?,
+                    kind: row.get(2)?,
+                    front: row.get(3)?,
+                    back: row.get(4)?,
+                    ease: row.get(5)?,
+                    interval: row.get(6)?,
+                    repetitions: row.get(7)?,
+                    due: row.get(8)?,
+                })
+            })?
+            .collect::<Result<Vec<Card>>>()?;
+
+        Ok(cards)
+    }
+
+    /// Every cached conjugation of a language, skipping rows that no longer parse.
+    fn cached_conjugations(&self, language: &str) -> Result<Vec<VerbConjugations>> {
+        let connection = self.connection();
+        let mut statement = connection.prepare(
+            "SELECT verb_conjugations \
+            FROM conjugations \
+            WHERE language = ?1 \
+            ORDER BY verb",
+        )?;
+
+        let conjugations = statement
+            .query_map([language], |row| row.get::<_, String>(0))?
+            .filter_map(|row| row.ok())
+            .filter_map(|json| VerbConjugations::from_cache(&json, language).ok())
+            .collect();
+
+        Ok(conjugations)
+    }
+
+    /// Every cached definition to or from a language, skipping rows that no
+    /// longer parse.
+    fn cached_definitions(&self, language: &str) -> Result<Vec<WordDefinitions>> {
+        let connection = self.connection();
+        let mut statement = connection.prepare(
+            "SELECT word, word_definitions \
+            FROM definitions \
+            WHERE to_language = ?1 OR from_language = ?1 \
+            ORDER BY word",
+        )?;
+
+        let definitions = statement
+            .query_map([language], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))?
+            .filter_map(|row| row.ok())
+            .filter_map(|(word, json)| {
+                let mut definitions: WordDefinitions = serde_json::from_str(&json).ok()?;
+                definitions.word = word;
+                Some(definitions)
+            })
+            .collect();
+
+        Ok(definitions)
+    }
+
+    /// Classifies every cached verb of a language, keeping those that
+    /// follow the model verb.
+    fn cached_verbs_like(
+        &self,
+        model: &str,
+        language: &str,
+    ) -> Result<Vec<(String, Classification)>> {
+        let verbs = self.cached_conjugations(language)?
+            .into_iter()
+            .filter_map(|conjugations| {
+                let classification = classification::classify(&conjugations, language)?;
+                match classification.model.as_deref() == Some(model) {
+                    true => Some((conjugations.verb, classification)),
+                    false => None,
+                }
+            })
+            .collect();
+
+        Ok(verbs)
+    }
+
+    async fn load_translation_table(&self, word: String, to_language: String, from_language: String, kind: &str) {
+        // def translates into the language learnt, trans out of it
+        let language = match kind {
+            "def" => to_language.clone(),
+            _ => from_language.clone(),
+        };
+
         match self.attempt_word_definition(
+            word,
             from_language,
             to_language,
         ).await {
             Err(err) => {
-                let mut app = self.app.lock().await;
-                app.set_error(err);
+                self.show_error(err.into()).await;
             }
             Ok(tables) => {
-                let mut app_obj = self.app.lock().await;
-                app_obj.set_definitions(tables);
+                self.offer_card(Card::from_definitions(&tables, kind, &language));
+
+                self.update(move |app_obj| app_obj.set_definitions(tables)).await;
             }
         };
     }
 
-    async fn handle_word_definition(&mut self) {
-        let app_obj = self.app.lock().await;
-        let to_language = app_obj.language.clone();
-        drop(app_obj);
+    async fn handle_word_definition(&self, request: LookupRequest) {
+        let word = request.body();
+        let to_language = request.language;
+        let from_language = request.native_language;
 
-        let from_language = "english".to_string();
-        self.load_translation_table(to_language.clone(), from_language.clone()).await;
+        self.load_translation_table(word, to_language, from_language, "def").await;
     }
 
-    async fn handle_word_translation(&mut self) {
-        let app_obj = self.app.lock().await;
-        let from_language = app_obj.language.clone();
-        drop(app_obj);
+    async fn handle_word_translation(&self, request: LookupRequest) {
+        let word = request.body();
+        let from_language = request.language;
+        let to_language = request.native_language;
 
-        let to_language = "english".to_string();
-        self.load_translation_table(to_language.clone(), from_language.clone()).await;
+        self.load_translation_table(word, to_language, from_language, "trans").await;
     }
 
-    async fn attempt_verb_lookup(&mut self) -> Result<VerbConjugations, UserError> {
-        let mut app_obj = self.app.lock().await;
-        let mut verb = app_obj.command_body();
-        app_obj.clear_input();
-
-        let language = app_obj.language.clone();
-
-        drop(app_obj);
-
+    async fn attempt_verb_lookup(
+        &self,
+        mut verb: String,
+        language: String,
+        offline_fallback: bool,
+    ) -> Result<VerbConjugations, AppError> {
         let rootword_result = self.map_word_to_root(
             verb.clone(),
             language.clone(),
@@ -208,120 +881,230 @@ impl LookupEventHandler {
             verb = rootword;
         }
 
-        let cached_conjugations = self.cached_verb_conjugation(verb.clone(), language.clone());
+        // a row that no longer parses is fetched again, as if it were missing
+        let cached_conjugations = self.cached_verb_conjugation(verb.clone(), language.clone())
+            .ok()
+            .and_then(|conjugations_str| VerbConjugations::from_cache(&conjugations_str, &language).ok());
 
-        match cached_conjugations {
-            Ok(conjugations_str) => {
-                let conjugations = serde_json::from_str(&conjugations_str.clone())
-                    .expect("Deserialized conjugations");
+        let progress = self.progress();
+        if let Some(conjugations) = cached_conjugations {
+            progress(LoadStage::CacheHit);
+            return Ok(conjugations);
+        }
 
-                Ok(conjugations)
-            },
+        let client = self.client.clone();
+        let fetch = {
+            let (verb, language) = (verb.clone(), language.clone());
+            move |progress: Progress| async move {
+                VerbConjugations::get_conjugation_tables(&verb, &language, &client, &progress).await
+            }.boxed()
+        };
+        let fetched = coalesce(
+            &self.conjugation_fetches,
+            (language.clone(), verb.clone()),
+            progress.clone(),
+            fetch,
+        ).await;
 
-            Err(_err) => {
-                let conjugations = VerbConjugations::get_conjugation_tables(
-                    verb.as_str(),
-                    language.as_str(),
-                    &self.client,
-                ).await?;
-
-                let infinitive = conjugations.verb.to_string();
-
-                // Add the conjugation to the database
-                let conjugations_json = serde_json::to_string(&conjugations.clone())
-                    .expect("Serialized conjugations");
-
-                self.connection.execute(
-                    "INSERT INTO conjugations \
-                    (language, verb, verb_conjugations) \
-                    values (?1, ?2, ?3)",
-                    &[
-                        &language.to_string(),
-                        &infinitive.to_string(),
-                        &conjugations_json.to_string()
-                    ],
-                ).expect("Inserted conjugation into the database");
-
-                let conjugation_tables = conjugations.conjugation_tables.clone();
-                for table in conjugation_tables {
-                    for conjugation_tup in table.conjugations {
-                        let conjugation = &conjugation_tup[1];
-
-                        if !conjugation.contains(' ') {
-                            self.connection.execute(
-                                "INSERT INTO rootwords \
-                                (language, word, rootword) \
-                                values (?1, ?2, ?3)",
-                                &[
-                                    &language.to_string(),
-                                    &conjugation.to_string(),
-                                    &infinitive.to_string(),
-                                ],
-                            ).expect("Inserted definition into the database");
-                        }
-                    }
-                }
+        let conjugations = match fetched {
+            Ok(conjugations) => conjugations,
 
-                Ok(conjugations)
-            },
+            // conjugate offline while the site is out of reach, without caching the
+            // unverified result; a verb it doesn't know is still reported as unknown
+            Err(err) if offline_fallback && err.is_unreachable() => {
+                progress(LoadStage::Generating);
+                return generator::generate_conjugations(&language, &verb).ok_or(err);
+            }
+            Err(err) => return Err(err),
+        };
+
+        let infinitive = conjugations.verb.to_string();
+
+        // Add the conjugation to the database
+        let conjugations_json = serde_json::to_string(&conjugations.clone())?;
+
+        // lookups sharing a fetch all get here, only the first caches it
+        let connection = self.connection();
+        let cached_row = connection.query_row(
+            "SELECT verb_conjugations FROM conjugations WHERE language = ?1 AND verb = ?2",
+            [&language, &infinitive],
+            |row| row.get::<_, String>(0),
+        ).ok();
+
+        match cached_row {
+            Some(json) if VerbConjugations::from_cache(&json, &language).is_ok() => return Ok(conjugations),
+            Some(_corrupt) => {
+                connection.execute(
+                    "UPDATE conjugations \
+                    SET verb_conjugations = ?3 \
+                    WHERE language = ?1 AND verb = ?2",
+                    [&language, &infinitive, &conjugations_json],
+                )?;
+                return Ok(conjugations);
+            }
+            None => {}
+        }
+
+        connection.execute(
+            "INSERT INTO conjugations \
+            (language, verb, verb_conjugations) \
+            values (?1, ?2, ?3)",
+            [
+                &language.to_string(),
+                &infinitive.to_string(),
+                &conjugations_json.to_string()
+            ],
+        )?;
+
+        let conjugation_tables = conjugations.conjugation_tables.clone();
+        for table in conjugation_tables {
+            for conjugated_form in table.forms {
+                let conjugation = &conjugated_form.form;
+
+                if !conjugation.contains(' ') {
+                    connection.execute(
+                        "INSERT INTO rootwords \
+                        (language, word, rootword) \
+                        values (?1, ?2, ?3)",
+                        [
+                            &language.to_string(),
+                            &conjugation.to_string(),
+                            &infinitive.to_string(),
+                        ],
+                    )?;
+                }
+            }
         }
+
+        Ok(conjugations)
     }
 
     async fn attempt_word_definition(
-        &mut self,
+        &self,
+        word: String,
         to_language: String,
         from_language: String,
-    ) -> Result<WordDefinitions, UserError> {
-        let mut app_obj = self.app.lock().await;
-        let word = app_obj.command_body();
-        app_obj.clear_input();
-        drop(app_obj);
-
+    ) -> Result<WordDefinitions, AppError> {
+        // a row that no longer parses is fetched again, as if it were missing
         let cached_definitions = self.cached_word_definition(
             word.clone(), to_language.clone(), from_language.clone()
-        );
+        )
+            .ok()
+            .and_then(|definitions_str| serde_json::from_str::<WordDefinitions>(&definitions_str).ok());
 
-        match cached_definitions {
-            Ok(definitions_str) => {
-                let definitions = serde_json::from_str(&definitions_str.clone())
-                    .expect("Deserialized definitions");
+        let progress = self.progress();
+        if let Some(mut definitions) = cached_definitions {
+            progress(LoadStage::CacheHit);
+            definitions.word = word;
+            return Ok(definitions);
+        }
 
-                Ok(definitions)
-            },
+        let client = self.client.clone();
+        let fetch = {
+            let (word, to_language, from_language) = (word.clone(), to_language.clone(), from_language.clone());
+            move |progress: Progress| async move {
+                WordDefinitions::get_definition_tables(to_language, from_language, word, &client, &progress).await
+            }.boxed()
+        };
+        let tables = coalesce(
+            &self.definition_fetches,
+            (word.clone(), to_language.clone(), from_language.clone()),
+            progress,
+            fetch,
+        ).await?;
 
-            Err(_err) => {
-                let tables = WordDefinitions::get_definition_tables(
-                    to_language.clone(),
-                    from_language.clone(),
-                    word.clone(),
-                    &self.client,
-                )
-                    .await;
-
-                if let Err(user_error) = tables {
-                    return Err(user_error);
-                }
+        // Add the conjugation to the database
+        let tables_json = serde_json::to_string(&tables.clone())?;
+
+        // lookups sharing a fetch all get here, only the first caches it
+        let connection = self.connection();
+        let cached_row = connection.query_row(
+            "SELECT word_definitions FROM definitions \
+            WHERE word = ?1 AND to_language = ?2 AND from_language = ?3",
+            [&word, &to_language, &from_language],
+            |row| row.get::<_, String>(0),
+        ).ok();
 
-                let tables = tables?;
-
-                // Add the conjugation to the database
-                let tables_json = serde_json::to_string(&tables.clone())
-                    .expect("Serialized definitions");
-
-                self.connection.execute(
-                    "INSERT INTO definitions \
-                    (word, to_language, from_language, word_definitions) \
-                    values (?1, ?2, ?3, ?4)",
-                    &[
-                        &word.to_string(),
-                        &to_language.to_string(),
-                        &from_language.to_string(),
-                        &tables_json.to_string()
-                    ],
-                ).expect("Inserted definition into the database");
-
-                Ok(tables)
+        match cached_row {
+            Some(json) if serde_json::from_str::<WordDefinitions>(&json).is_ok() => return Ok(tables),
+            Some(_corrupt) => {
+                connection.execute(
+                    "UPDATE definitions \
+                    SET word_definitions = ?4 \
+                    WHERE word = ?1 AND to_language = ?2 AND from_language = ?3",
+                    [&word, &to_language, &from_language, &tables_json],
+                )?;
+                return Ok(tables);
             }
+            None => {}
+        }
+
+        connection.execute(
+            "INSERT INTO definitions \
+            (word, to_language, from_language, word_definitions) \
+            values (?1, ?2, ?3, ?4)",
+            [
+                &word.to_string(),
+                &to_language.to_string(),
+                &from_language.to_string(),
+                &tables_json.to_string()
+            ],
+        )?;
+
+        Ok(tables)
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    use crate::{config::Config, http::HttpConfig};
+
+    #[tokio::test]
+    async fn updates_of_cancelled_lookups_are_dropped() {
+        let (app_tx, mut app_rx) = tokio::sync::mpsc::channel(4);
+        let client = HttpClient::new(&HttpConfig::default()).expect("an http client");
+        let connection = LookupEventHandler::init_db(Path::new(":memory:")).expect("a database");
+        let handler = Arc::new(LookupEventHandler::new(app_tx, client, connection));
+        let mut app = App::new(handler.clone(), &Config::default());
+
+        let generation = app.lookup_generation();
+        GENERATION.scope(generation, handler.update(|app| app.language = "stale".to_string())).await;
+        handler.update(|app| app.native_language = "write".to_string()).await;
+        app.cancel_lookups();
+
+        while let Ok(AppEvent::Update(update)) = app_rx.try_recv() {
+            update(&mut app);
         }
+        assert_eq!(app.language, "french");
+        assert_eq!(app.native_language, "write");
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn lookups_sharing_a_fetch_all_see_its_stages() {
+        let in_flight: InFlight<&str, u32, ()> = Default::default();
+        let seen = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let watcher = |name: &'static str| -> Progress {
+            let seen = Arc::clone(&seen);
+            Arc::new(move |stage: LoadStage| seen.lock().expect("seen lock").push(format!("{name} {stage:?}")))
+        };
+
+        let (go_tx, go_rx) = tokio::sync::oneshot::channel::<()>();
+        let first = coalesce(&in_flight, "parler", watcher("first"), |progress| async move {
+            progress(LoadStage::Parsing);
+            let _ = go_rx.await;
+            progress(LoadStage::Generating);
+            Ok(1)
+        }.boxed());
+        let second = coalesce(&in_flight, "parler", watcher("second"), |_| async { Ok(2) }.boxed());
+
+        let (first, second, _) = futures::join!(first, second, async { go_tx.send(()) });
+        assert_eq!((first, second), (Ok(1), Ok(1)));
+        assert_eq!(
+            *seen.lock().expect("seen lock"),
+            ["first Parsing", "second Parsing", "first Generating", "second Generating"],
+        );
+        assert!(in_flight.lock().expect("in-flight lock").is_empty());
+    }
+}
@@ -4,76 +4,117 @@ mod wordreference;
 mod app_event;
 mod lookup_event;
 mod user_error;
+mod app_error;
 mod definitions;
+mod grammar;
+mod comparison;
+mod conjugator;
+mod verb_models;
+mod generator;
+mod classification;
+mod variants;
+mod quiz;
+mod deck;
+mod export;
+mod batch;
+mod http;
+mod config;
+mod terminal;
+mod status;
+mod table_layout;
 
 use app::{App, run_app};
 use app_event::{AppEventHandler, AppEvent};
-use lookup_event::{LookupEventHandler, LookupEvent};
+use lookup_event::LookupEventHandler;
+use http::HttpClient;
+use config::Config;
+use app_error::AppError;
 
-use crossterm::{
-    event::{DisableMouseCapture, EnableMouseCapture},
-    execute,
-    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
-};
-use tokio::sync::mpsc::channel;
-use std::{error::Error, io, sync::Arc, process::exit};
+use terminal::TerminalGuard;
+
+use tokio::sync::{mpsc::channel, watch};
+use std::{error::Error, io, path::PathBuf, sync::Arc, process::exit};
+use rusqlite::Connection;
 use tui::{backend::CrosstermBackend, Terminal,};
 
-async fn start_app() -> Result<(), Box<dyn Error>> {
-    let (sync_io_tx, mut sync_io_rx) = channel::<AppEvent>(512);
-    let (sync_lookup_tx, mut sync_lookup_rx) = channel::<LookupEvent>(512);
+async fn start_app(config: Config, client: HttpClient, connection: Connection) -> Result<(), Box<dyn Error>> {
+    // key presses and lookup results all reach the app through one channel
+    let (app_tx, app_rx) = channel::<AppEvent>(512);
+    let (shutdown_tx, shutdown_rx) = watch::channel(false);
 
-    // setup terminal
-    enable_raw_mode()?;
-    let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
-    let backend = CrosstermBackend::new(stdout);
+    // setup terminal, restored when the guard drops or on a panic
+    let terminal_guard = TerminalGuard::enter()?;
+    let backend = CrosstermBackend::new(io::stdout());
     let mut terminal = Terminal::new(backend)?;
 
+    tokio::spawn(terminal::shutdown_on_signal(shutdown_tx));
+
     // create app and run it
-    let app = Arc::new(
-        tokio::sync::Mutex::new(
-            App::new(
-                sync_io_tx.clone(),
-                sync_lookup_tx.clone(),
-            )
-        )
-    );
-    let app_io = Arc::clone(&app);
-    let app_lookup = Arc::clone(&app);
-    let app_ui = Arc::clone(&app);
+    let lookup_handler = Arc::new(LookupEventHandler::new(app_tx.clone(), client, connection));
+    let mut app = App::new(lookup_handler, &config);
+    let handler = AppEventHandler::new(config.keys.clone());
 
-    tokio::spawn(async move {
-        let mut handler = AppEventHandler::new(app_io);
-        while let Some(app_event) = sync_io_rx.recv().await {
-            handler.handle_app_event(app_event).await;
-        }
-    });
+    let result = run_app(&mut terminal, &mut app, app_rx, app_tx, handler, shutdown_rx).await;
 
-    tokio::spawn(async move {
-        let mut handler = LookupEventHandler::new(app_lookup);
-        while let Some(lookup_event) = sync_lookup_rx.recv().await {
-            handler.handle_lookup_event(lookup_event).await;
-        }
-    });
+    // restore terminal, then let lookups in progress finish their writes
+    drop(terminal);
+    drop(terminal_guard);
+    terminal::finish_lookups(app.take_lookups()).await;
 
-    run_app(&mut terminal, &app_ui).await?;
+    Ok(result?)
+}
 
-    // restore terminal
-    disable_raw_mode()?;
-    execute!(
-        terminal.backend_mut(),
-        LeaveAlternateScreen,
-        DisableMouseCapture
-    )?;
-    terminal.show_cursor()?;
+/// Takes `--db <path>` or `--db=<path>` out of the arguments.
+fn take_db_flag(args: &mut Vec<String>) -> Result<Option<PathBuf>, AppError> {
+    let Some(index) = args.iter().position(|arg| arg == "--db" || arg.starts_with("--db=")) else {
+        return Ok(None);
+    };
 
-    Ok(())
+    let flag = args.remove(index);
+    let path = match flag.strip_prefix("--db=") {
+        Some(path) => path.to_string(),
+        None if index < args.len() => args.remove(index),
+        None => String::new(),
+    };
+    match path.is_empty() {
+        true => Err(AppError::Config { message: "Usage: --db <path>, or --db :memory:".to_string() }),
+        false => Ok(Some(PathBuf::from(path))),
+    }
 }
 
 #[tokio::main]
 async fn main() {
-    if let Err(err) = start_app().await {
+    let mut args = std::env::args().skip(1).collect::<Vec<String>>();
+    let setup = Config::load().and_then(|config| {
+        let client = HttpClient::new(&config.network.http_config().with_env())?;
+        let db_path = config.cache.db_path(take_db_flag(&mut args)?)?;
+        let connection = LookupEventHandler::init_db(&db_path)?;
+        Ok((config, client, connection))
+    });
+    let (config, client, connection) = match setup {
+        Ok(setup) => setup,
+        Err(err) => {
+            eprintln!("{err}");
+            exit(2);
+        }
+    };
+
+    if args.first().is_some_and(|command| command == "batch") {
+        let result = match batch::BatchOptions::parse(&args[1..], &config) {
+            Ok(options) => batch::run_batch(options, client, connection).await,
+            Err(err) => Err(err),
+        };
+        match result {
+            Ok(0) => exit(0),
+            Ok(_num_failed) => exit(1),
+            Err(err) => {
+                eprintln!("{}", err.message);
+                exit(2);
+            }
+        }
+    }
+
+    if let Err(err) = start_app(config, client, connection).await {
         eprintln!("{}", err);
         exit(1);
     } else {
@@ -0,0 +1,342 @@
+use rand::seq::SliceRandom;
+
+use crate::{
+    conjugations::VerbConjugations,
+    conjugator,
+    grammar,
+    user_error::UserError,
+    wordreference::wordreference_utils,
+};
+
+pub struct QuizOptions {
+    pub language: String,
+    pub tenses: Vec<String>, // tense queries such as "subj imparfait"
+    pub verbs: Vec<String>,
+    pub lenient: bool, // accept answers that only miss accents
+}
+
+impl QuizOptions {
+    /// Parses the arguments of `quiz`: "-l" for lenient grading,
+    /// "lang=<language>", "tenses=<tense>,<tense>" with "_" for spaces,
+    /// e.g. "tenses=pres,subj_imp", and any verbs to restrict the quiz to.
+    pub fn parse(args: Vec<String>, default_language: &str) -> Result<QuizOptions, UserError> {
+        let mut options = QuizOptions {
+            language: default_language.to_string(),
+            tenses: Vec::new(),
+            verbs: Vec::new(),
+            lenient: false,
+        };
+
+        for arg in args {
+            if arg == "-l" {
+                options.lenient = true;
+            } else if let Some(language) = arg.strip_prefix("lang=") {
+                options.language = wordreference_utils::parse_language(language)
+                    .ok_or_else(|| UserError {
+                        message: format!("Unsupported language '{language}' in '{arg}'"),
+                    })?;
+            } else if let Some(tenses) = arg.strip_prefix("tenses=") {
+                options.tenses.extend(
+                    tenses
+                        .split(',')
+                        .filter(|tense| !tense.is_empty())
+                        .map(|tense| tense.replace('_', " ")),
+                );
+            } else if arg.starts_with('-') {
+                return Err(UserError {
+                    message: format!(
+                        "Unknown quiz option '{arg}', try 'quiz [-l] [lang=<language>] \
+                        [tenses=<tense>,<tense>] [verb ...]'",
+                    ),
+                });
+            } else {
+                options.verbs.push(arg.to_lowercase());
+            }
+        }
+
+        Ok(options)
+    }
+}
+
+#[derive(Clone)]
+pub struct QuizQuestion {
+    pub verb: String,
+    pub tense: String,
+    pub pronoun: String,
+    pub answer: String, // the whole table cell, which can list several forms
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Grade {
+    Correct,
+    WrongAccents, // right but for accents, e.g. "parle" for "parlé"
+    Wrong,
+}
+
+pub struct QuizResult {
+    pub question: QuizQuestion,
+    pub typed: String,
+    pub grade: Grade,
+    pub counted_correct: bool,
+}
+
+/// A drill over cached conjugations, asking for one form at a time.
+pub struct Quiz {
+    pub options: QuizOptions,
+    verbs: Vec<VerbConjugations>,
+    candidates: Vec<(usize, usize)>, // (verb, table) pairs to ask from
+    pub question: QuizQuestion,
+    pub last_result: Option<QuizResult>,
+    pub correct: u32,
+    pub answered: u32,
+}
+
+impl Quiz {
+    pub fn new(options: QuizOptions, verbs: Vec<VerbConjugations>) -> Result<Quiz, UserError> {
+        let verbs = verbs
+            .into_iter()
+            .filter(|conjugations| {
+                options.verbs.is_empty()
+                    || options.verbs.iter().any(|verb| grammar::fold(verb) == grammar::fold(&conjugations.verb))
+            })
+            .collect::<Vec<VerbConjugations>>();
+
+        if verbs.is_empty() {
+            return Err(UserError {
+                message: format!(
+                    "No cached {} verbs to quiz on, look some up with conj first",
+                    options.language,
+                ),
+            });
+        }
+
+        let mut candidates: Vec<(usize, usize)> = Vec::new();
+        for (verb_index, conjugations) in verbs.iter().enumerate() {
+            let table_indices = match options.tenses.is_empty() {
+                true => (0..conjugations.conjugation_tables.len()).collect::<Vec<usize>>(),
+                false => options.tenses
+                    .iter()
+                    .filter_map(|tense| conjugations.find_table(tense, &options.language))
+                    .collect(),
+            };
+
+            for table_index in table_indices {
+                let table = &conjugations.conjugation_tables[table_index];
+                let askable = table.forms.iter().any(|form| Quiz::askable(&form.form));
+                if askable && !candidates.contains(&(verb_index, table_index)) {
+                    candidates.push((verb_index, table_index));
+                }
+            }
+        }
+
+        if candidates.is_empty() {
+            return Err(UserError {
+                message: format!(
+                    "None of the cached verbs have the tenses '{}'",
+                    options.tenses.join(", "),
+                ),
+            });
+        }
+
+        let question = Quiz::pick_question(&verbs, &candidates);
+        Ok(Quiz {
+            options,
+            verbs,
+            candidates,
+            question,
+            last_result: None,
+            correct: 0,
+            answered: 0,
+        })
+    }
+
+    fn askable(form: &str) -> bool {
+        !form.trim().is_empty() && form.trim() != "-"
+    }
+
+    fn pick_question(verbs: &[VerbConjugations], candidates: &[(usize, usize)]) -> QuizQuestion {
+        let mut rng = rand::thread_rng();
+        let (verb_index, table_index) = *candidates
+            .choose(&mut rng)
+            .expect("a quiz has at least one table to ask from");
+
+        let conjugations = &verbs[verb_index];
+        let table = &conjugations.conjugation_tables[table_index];
+        let forms = table.forms
+            .iter()
+            .filter(|form| Quiz::askable(&form.form))
+            .collect::<Vec<_>>();
+        let form = forms
+            .choose(&mut rng)
+            .expect("candidate tables have a form to ask for");
+
+        QuizQuestion {
+            verb: conjugations.verb.clone(),
+            tense: table.label(),
+            pronoun: form.pronoun.clone(),
+            answer: form.form.clone(),
+        }
+    }
+
+    /// Grades the typed answer and moves on to the next question. An empty
+    /// answer gives up on the question.
+    pub fn answer(&mut self, typed: &str) {
+        let grade = Quiz::grade(&self.question, typed);
+        let counted_correct = grade == Grade::Correct
+            || (grade == Grade::WrongAccents && self.options.lenient);
+
+        self.answered += 1;
+        if counted_correct {
+            self.correct += 1;
+        }
+
+        let question = Quiz::pick_question(&self.verbs, &self.candidates);
+        self.last_result = Some(QuizResult {
+            question: std::mem::replace(&mut self.question, question),
+            typed: typed.trim().to_string(),
+            grade,
+            counted_correct,
+        });
+    }
+
+    fn grade(question: &QuizQuestion, typed: &str) -> Grade {
+        let typed = typed
+            .split_whitespace()
+            .collect::<Vec<&str>>()
+            .join(" ")
+            .to_lowercase();
+
+        // the pronoun is optional, "nous parlons" and "parlons" both count
+        let pronouns = question.pronoun
+            .split(',')
+            .map(|pronoun| pronoun.trim().trim_matches(['(', ')']).to_lowercase())
+            .filter(|pronoun| !pronoun.is_empty())
+            .collect::<Vec<String>>();
+        let typed = pronouns
+            .iter()
+            .find_map(|pronoun| match pronoun.ends_with(['\'', '’']) {
+                true => typed.strip_prefix(pronoun.as_str()),
+                false => typed.strip_prefix(&format!("{pronoun} ")),
+            })
+            .unwrap_or(&typed)
+            .trim()
+            .to_string();
+
+        if typed.is_empty() {
+            return Grade::Wrong;
+        }
+
+        let expected = conjugator::alternatives(&question.answer)
+            .into_iter()
+            .map(|form| form.to_lowercase())
+            .collect::<Vec<String>>();
+
+        if expected.contains(&typed) {
+            Grade::Correct
+        } else if expected.iter().any(|form| grammar::fold(form) == grammar::fold(&typed)) {
+            Grade::WrongAccents
+        } else {
+            Grade::Wrong
+        }
+    }
+
+    pub fn score(&self) -> String {
+        format!("{}/{} correct", self.correct, self.answered)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::conjugations::ConjugationTable;
+
+    fn question(pronoun: &str, answer: &str) -> QuizQuestion {
+        QuizQuestion {
+            verb: "parler".to_string(),
+            tense: "indicatif présent".to_string(),
+            pronoun: pronoun.to_string(),
+            answer: answer.to_string(),
+        }
+    }
+
+    fn grade(pronoun: &str, answer: &str, typed: &str) -> Grade {
+        Quiz::grade(&question(pronoun, answer), typed)
+    }
+
+    #[test]
+    fn answers_are_graded_by_form_then_accents() {
+        assert_eq!(grade("nous", "parlons", "parlons"), Grade::Correct);
+        assert_eq!(grade("nous", "parlons", "  Parlons "), Grade::Correct);
+        assert_eq!(grade("yo", "hablé", "hable"), Grade::WrongAccents);
+        assert_eq!(grade("yo", "hablé", "hablo"), Grade::Wrong);
+        assert_eq!(grade("yo", "hablé", ""), Grade::Wrong);
+    }
+
+    #[test]
+    fn the_pronoun_is_optional() {
+        assert_eq!(grade("nous", "parlons", "nous parlons"), Grade::Correct);
+        assert_eq!(grade("il, elle, on", "parle", "elle parle"), Grade::Correct);
+        assert_eq!(grade("(tu)", "parle", "tu parle"), Grade::Correct);
+        assert_eq!(grade("que je", "parle", "que je parle"), Grade::Correct);
+        assert_eq!(grade("nous", "parlons", "nous"), Grade::Wrong);
+    }
+
+    #[test]
+    fn an_elided_pronoun_sticks_to_the_form() {
+        assert_eq!(grade("j'", "aime", "j'aime"), Grade::Correct);
+        assert_eq!(grade("j'", "aime", "aime"), Grade::Correct);
+        assert_eq!(grade("j'", "ai parlé", "j'ai parlé"), Grade::Correct);
+    }
+
+    #[test]
+    fn any_listed_form_counts_but_agreement_endings_do_not() {
+        assert_eq!(grade("yo", "hablara o hablase", "hablase"), Grade::Correct);
+        assert_eq!(grade("je", "suis allé(e)", "je suis allé"), Grade::Correct);
+        assert_eq!(grade("je", "suis allé/e", "je suis allé"), Grade::Correct);
+        assert_eq!(grade("je", "suis allé/e", "e"), Grade::Wrong);
+        assert_eq!(grade("io", "sono andato/a", "sono andato"), Grade::Correct);
+        assert_eq!(grade("io", "sono andato/a", "a"), Grade::Wrong);
+    }
+
+    fn quiz(lenient: bool) -> Quiz {
+        let mut conjugations = VerbConjugations::empty();
+        conjugations.verb = "parler".to_string();
+        conjugations.conjugation_tables.push(ConjugationTable::from_pairs(
+            "indicatif",
+            "passé composé",
+            vec![("j'".to_string(), "ai parlé".to_string())],
+        ));
+
+        let options = QuizOptions {
+            language: "french".to_string(),
+            tenses: Vec::new(),
+            verbs: Vec::new(),
+            lenient,
+        };
+        Quiz::new(options, vec![conjugations]).expect("a table to ask from")
+    }
+
+    #[test]
+    fn lenient_quizzes_count_missing_accents_as_correct() {
+        let mut strict = quiz(false);
+        strict.answer("ai parle");
+        assert_eq!(strict.score(), "0/1 correct");
+
+        let mut lenient = quiz(true);
+        lenient.answer("ai parle");
+        assert_eq!(lenient.score(), "1/1 correct");
+        assert_eq!(lenient.last_result.as_ref().map(|result| result.grade), Some(Grade::WrongAccents));
+    }
+
+    #[test]
+    fn options_parse_flags_languages_and_tenses() {
+        let args = ["-l", "lang=es", "tenses=pres,subj_imp", "Hablar"].map(String::from).to_vec();
+        let options = QuizOptions::parse(args, "french").expect("valid options");
+        assert!(options.lenient);
+        assert_eq!(options.language, "spanish");
+        assert_eq!(options.tenses, vec!["pres", "subj imp"]);
+        assert_eq!(options.verbs, vec!["hablar"]);
+
+        assert!(QuizOptions::parse(vec!["-x".to_string()], "french").is_err());
+    }
+}
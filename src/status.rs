@@ -0,0 +1,90 @@
+use std::{sync::Arc, time::Duration};
+
+use tokio::time::Instant;
+
+use crate::config::KeyBinding;
+
+/// What a lookup is doing, as it reports it.
+#[derive(Clone, Debug)]
+pub enum LoadStage {
+    CacheHit,
+    Connecting { host: String },
+    Downloading { host: String, bytes: usize },
+    Parsing,
+    Generating, // conjugating offline after a failed fetch
+    Failed,
+}
+
+impl LoadStage {
+    fn describe(&self) -> String {
+        match self {
+            LoadStage::CacheHit => "found in the cache".to_string(),
+            LoadStage::Connecting { host } => format!("connecting to {host}"),
+            LoadStage::Downloading { host, bytes } => {
+                format!("downloading {} KB from {host}", bytes.div_ceil(1024))
+            }
+            LoadStage::Parsing => "parsing".to_string(),
+            LoadStage::Generating => "conjugating offline".to_string(),
+            LoadStage::Failed => "a lookup failed".to_string(),
+        }
+    }
+
+    /// Where a result reached at this stage came from.
+    fn source(&self) -> &'static str {
+        match self {
+            LoadStage::CacheHit => "from the cache",
+            LoadStage::Connecting { .. } | LoadStage::Downloading { .. } | LoadStage::Parsing => "from the network",
+            LoadStage::Generating => "generated offline",
+            LoadStage::Failed => "failed",
+        }
+    }
+}
+
+/// Reports the stages of a lookup as they happen.
+pub type Progress = Arc<dyn Fn(LoadStage) + Send + Sync>;
+
+/// What the lookups in progress are doing, or how the last ones went.
+pub struct Status {
+    stage: Option<LoadStage>, // the latest any lookup reported
+    started: Instant,
+    took: Option<Duration>, // once every lookup is done
+}
+
+impl Status {
+    pub fn new() -> Status {
+        Status {
+            stage: None,
+            started: Instant::now(),
+            took: None,
+        }
+    }
+
+    pub fn start(&mut self) {
+        *self = Status::new();
+    }
+
+    pub fn report(&mut self, stage: LoadStage) {
+        self.stage = Some(stage);
+    }
+
+    pub fn finish(&mut self) {
+        self.took = Some(self.started.elapsed());
+    }
+
+    /// e.g. "downloading 12 KB from www.wordreference.com · 0.8s · french ↔ english · Esc to cancel",
+    /// or once done "from the network · 1.2s · french ↔ english".
+    pub fn line(&self, language: &str, native_language: &str, cancel_key: KeyBinding) -> String {
+        let languages = format!("{language} ↔ {native_language}");
+        let seconds = |duration: Duration| format!("{:.1}s", duration.as_secs_f64());
+
+        match (&self.stage, self.took) {
+            (stage, None) => format!(
+                "{} · {} · {languages} · {cancel_key} to cancel",
+                stage.as_ref().map(|stage| stage.describe()).unwrap_or_else(|| "starting".to_string()),
+                seconds(self.started.elapsed()),
+            ),
+            (Some(stage), Some(took)) => format!("{} · {} · {languages}", stage.source(), seconds(took)),
+            (None, Some(took)) => format!("done · {} · {languages}", seconds(took)),
+        }
+    }
+}
@@ -0,0 +1,150 @@
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// Narrower than this, a column's text wraps a word or two per line at best.
+const MIN_COLUMN_WIDTH: u16 = 6;
+
+/// How wide a line of text is drawn, wide characters taking two cells.
+pub fn display_width(text: &str) -> u16 {
+    text.lines().map(|line| line.width()).max().unwrap_or(0) as u16
+}
+
+/// Widths for the columns of a table, from their content, to fit a width with
+/// `spacing` cells between columns. Columns narrower than an even share keep
+/// their width and the others split the rest, wrapping their text. When even
+/// the minimum widths don't fit, the columns that don't are left out.
+pub fn column_widths(header: &[String], rows: &[Vec<String>], width: u16, spacing: u16) -> Vec<u16> {
+    let num_columns = rows.iter().map(|row| row.len()).chain([header.len()]).max().unwrap_or(0);
+    let natural = (0..num_columns)
+        .map(|column| {
+            rows.iter()
+                .map(|row| row.as_slice())
+                .chain(std::iter::once(header))
+                .filter_map(|row| row.get(column))
+                .map(|text| display_width(text))
+                .max()
+                .unwrap_or(0)
+                .max(1)
+        })
+        .collect::<Vec<u16>>();
+
+    // as many columns as fit at their minimum width
+    let mut num_shown = 0;
+    let mut needed = 0;
+    for width_needed in natural.iter().map(|natural| (*natural).min(MIN_COLUMN_WIDTH)) {
+        needed += width_needed + if num_shown > 0 { spacing } else { 0 };
+        if needed > width && num_shown > 0 {
+            break;
+        }
+        num_shown += 1;
+    }
+    let natural = &natural[..num_shown];
+
+    let available = width.saturating_sub(spacing * (num_shown as u16).saturating_sub(1));
+    let mut widths = vec![0; num_shown];
+    let mut by_width = (0..num_shown).collect::<Vec<usize>>();
+    by_width.sort_by_key(|column| natural[*column]);
+
+    let mut remaining = available;
+    for (index, column) in by_width.iter().enumerate() {
+        let share = remaining / (num_shown - index) as u16;
+        widths[*column] = natural[*column].min(share).max(1);
+        remaining = remaining.saturating_sub(widths[*column]);
+    }
+
+    // what is left over goes to the last column, so the table fills its block
+    if let Some(last) = widths.last_mut() {
+        *last += remaining;
+    }
+
+    widths
+}
+
+/// Breaks text into lines no wider than `width`, between words where it can.
+pub fn wrap(text: &str, width: u16) -> Vec<String> {
+    let width = width.max(1) as usize;
+    let mut lines = Vec::new();
+
+    for paragraph in text.lines() {
+        let mut line = String::new();
+        for word in paragraph.split_whitespace() {
+            let separator = if line.is_empty() { 0 } else { 1 };
+            if line.width() + separator + word.width() <= width {
+                if separator == 1 {
+                    line.push(' ');
+                }
+                line.push_str(word);
+                continue;
+            }
+
+            if !line.is_empty() {
+                lines.push(std::mem::take(&mut line));
+            }
+
+            // a word too long for a line of its own is split
+            for character in word.chars() {
+                // a character wider than the column still gets a line of its own
+                if !line.is_empty() && line.width() + character.width().unwrap_or(0) > width {
+                    lines.push(std::mem::take(&mut line));
+                }
+                line.push(character);
+            }
+        }
+        lines.push(line);
+    }
+
+    if lines.is_empty() {
+        lines.push(String::new());
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(texts: &[&str]) -> Vec<String> {
+        texts.iter().map(|text| text.to_string()).collect()
+    }
+
+    #[test]
+    fn columns_that_fit_keep_their_width_and_the_last_takes_the_rest() {
+        let widths = column_widths(&strings(&["a", "bb"]), &[strings(&["abc", "d"])], 20, 1);
+        assert_eq!(widths, vec![3, 16]);
+    }
+
+    #[test]
+    fn narrow_widths_shrink_the_widest_columns() {
+        let rows = [strings(&["je", "parle toujours beaucoup"])];
+        assert_eq!(column_widths(&strings(&["", "form"]), &rows, 12, 1), vec![2, 9]);
+        assert_eq!(column_widths(&strings(&["form"]), &[], 3, 1), vec![3]);
+    }
+
+    #[test]
+    fn wide_characters_take_two_cells() {
+        assert_eq!(display_width("日本\nabc"), 4);
+        assert_eq!(column_widths(&strings(&["日本語", "x"]), &[], 10, 1), vec![6, 3]);
+    }
+
+    #[test]
+    fn columns_that_do_not_fit_are_dropped() {
+        let header = strings(&["infinitive", "participle", "imperative"]);
+        assert_eq!(column_widths(&header, &[], 14, 2), vec![6, 6]);
+    }
+
+    #[test]
+    fn text_wraps_between_words() {
+        assert_eq!(wrap("parle toujours beaucoup", 9), strings(&["parle", "toujours", "beaucoup"]));
+        assert_eq!(wrap("a\nb", 5), strings(&["a", "b"]));
+        assert_eq!(wrap("", 5), strings(&[""]));
+    }
+
+    #[test]
+    fn long_words_are_split() {
+        assert_eq!(
+            wrap("je anticonstitutionnellement", 10),
+            strings(&["je", "anticonsti", "tutionnell", "ement"]),
+        );
+        assert_eq!(wrap("日本語", 4), strings(&["日本", "語"]));
+        assert_eq!(wrap("日本語", 1), strings(&["日", "本", "語"]));
+    }
+}
@@ -0,0 +1,82 @@
+use std::{io, panic, time::Duration};
+
+use crossterm::{
+    cursor::Show,
+    event::{DisableMouseCapture, EnableMouseCapture},
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use tokio::{sync::watch, task::JoinHandle};
+
+/// How long lookups still running at exit get to finish their writes.
+const SHUTDOWN_GRACE: Duration = Duration::from_secs(3);
+
+/// Puts the terminal in raw mode on the alternate screen, putting it back
+/// when dropped or when the program panics.
+pub struct TerminalGuard;
+
+impl TerminalGuard {
+    pub fn enter() -> io::Result<TerminalGuard> {
+        // a panic with `panic = "abort"` skips drops, but not the hook
+        let default_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            restore();
+            default_hook(info);
+        }));
+
+        enable_raw_mode()?;
+        if let Err(err) = execute!(io::stdout(), EnterAlternateScreen, EnableMouseCapture) {
+            restore();
+            return Err(err);
+        }
+
+        Ok(TerminalGuard)
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        restore();
+    }
+}
+
+/// Leaves raw mode and the alternate screen, ignoring failures since it runs
+/// while already shutting down.
+fn restore() {
+    let _ = disable_raw_mode();
+    let _ = execute!(io::stdout(), LeaveAlternateScreen, DisableMouseCapture, Show);
+}
+
+/// Asks every task to shut down on SIGINT or SIGTERM.
+pub async fn shutdown_on_signal(shutdown: watch::Sender<bool>) {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+
+        let Ok(mut terminate) = signal(SignalKind::terminate()) else {
+            return;
+        };
+        tokio::select! {
+            _ = tokio::signal::ctrl_c() => {}
+            _ = terminate.recv() => {}
+        }
+    }
+
+    #[cfg(not(unix))]
+    if tokio::signal::ctrl_c().await.is_err() {
+        return;
+    }
+
+    let _ = shutdown.send(true);
+}
+
+/// Waits for lookups still running, aborting those that outlast the grace
+/// period. Their database writes don't await, so an abort never splits one.
+pub async fn finish_lookups(mut lookups: Vec<JoinHandle<()>>) {
+    let all_finished = futures::future::join_all(lookups.iter_mut());
+    if tokio::time::timeout(SHUTDOWN_GRACE, all_finished).await.is_err() {
+        for lookup in lookups {
+            lookup.abort();
+        }
+    }
+}
@@ -0,0 +1,234 @@
+use crate::{
+    conjugations::{ConjugatedForm, ConjugationTable, VerbConjugations},
+    grammar::{Mood, Person, Tense},
+};
+
+/// Which sentence form conjugations are shown in. Both toggles together give
+/// the negative interrogative, e.g. "ne parle-t-il pas ?".
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct Variant {
+    pub negative: bool,
+    pub interrogative: bool,
+}
+
+impl Variant {
+    pub fn is_affirmative(&self) -> bool {
+        !self.negative && !self.interrogative
+    }
+
+    pub fn label(&self) -> &'static str {
+        match (self.negative, self.interrogative) {
+            (false, false) => "affirmative",
+            (true, false) => "negative",
+            (false, true) => "interrogative",
+            (true, true) => "negative interrogative",
+        }
+    }
+}
+
+/// Pronoun/conjugation rows of a table rewritten in a sentence form. Only
+/// the cached forms are used, the negative imperative of spanish borrowing
+/// from the subjunctive table.
+pub fn transform_table(
+    conjugations: &VerbConjugations,
+    table: &ConjugationTable,
+    language: &str,
+    variant: Variant,
+) -> Vec<Vec<String>> {
+    if variant.is_affirmative() {
+        return table.conjugations_as_strings();
+    }
+
+    table.forms
+        .iter()
+        .map(|form| {
+            let (pronoun, transformed) = transform_form(conjugations, table, form, language, variant);
+            vec![pronoun, transformed]
+        })
+        .collect()
+}
+
+fn transform_form(
+    conjugations: &VerbConjugations,
+    table: &ConjugationTable,
+    form: &ConjugatedForm,
+    language: &str,
+    variant: Variant,
+) -> (String, String) {
+    if form.form.is_empty() || form.form == "-" {
+        return (form.pronoun.clone(), form.form.clone());
+    }
+
+    // questions are only asked in the indicative and conditional
+    let variant = Variant {
+        interrogative: variant.interrogative
+            && matches!(table.mood, Mood::Indicative | Mood::Conditional),
+        ..variant
+    };
+    if variant.is_affirmative() {
+        return (form.pronoun.clone(), form.form.clone());
+    }
+
+    match language {
+        "french" => french(table, form, variant),
+        "spanish" => {
+            let cell = match (table.mood, variant.negative) {
+                // "¡habla!" becomes "no hables"
+                (Mood::Imperative, true) => subjunctive_form(conjugations, form.person)
+                    .unwrap_or_else(|| form.form.clone()),
+                _ => form.form.clone(),
+            };
+            let transformed = alternatives(&cell)
+                .iter()
+                .map(|alternative| match (variant.negative, variant.interrogative) {
+                    (true, true) => format!("¿no {alternative}?"),
+                    (true, false) => format!("no {alternative}"),
+                    _ => format!("¿{alternative}?"),
+                })
+                .collect::<Vec<String>>()
+                .join(", ");
+            (form.pronoun.clone(), transformed)
+        }
+        "italian" => {
+            let cell = match (table.mood, form.person, variant.negative) {
+                // "parla!" becomes "non parlare"
+                (Mood::Imperative, Some(Person::SecondSingular), true) => conjugations.verb.clone(),
+                _ => form.form.clone(),
+            };
+            let transformed = alternatives(&cell)
+                .iter()
+                .map(|alternative| match (variant.negative, variant.interrogative) {
+                    (true, true) => format!("non {alternative}?"),
+                    (true, false) => format!("non {alternative}"),
+                    _ => format!("{alternative}?"),
+                })
+                .collect::<Vec<String>>()
+                .join(", ");
+            (form.pronoun.clone(), transformed)
+        }
+        _ => (form.pronoun.clone(), form.form.clone()),
+    }
+}
+
+/// A cell can list several forms, e.g. "paie, paye".
+fn alternatives(cell: &str) -> Vec<String> {
+    cell.split(',')
+        .map(|part| part.trim_matches(|c: char| c == '¡' || c == '!' || c.is_whitespace()).to_string())
+        .filter(|part| !part.is_empty())
+        .collect()
+}
+
+fn subjunctive_form(conjugations: &VerbConjugations, person: Option<Person>) -> Option<String> {
+    conjugations.conjugation_tables
+        .iter()
+        .find(|table| table.mood == Mood::Subjunctive && table.tense_kind == Tense::Present)?
+        .forms
+        .iter()
+        .find(|form| person.is_some() && form.person == person)
+        .map(|form| form.form.clone())
+}
+
+fn starts_with_vowel(word: &str) -> bool {
+    word.starts_with(['a', 'e', 'i', 'o', 'u', 'h', 'â', 'ê', 'é', 'è', 'î', 'ô', 'û', 'y'])
+}
+
+/// Splits reflexive pronouns from the front of a verb: "me lève" gives
+/// ("me", "lève") and "m'appelle" gives ("m'", "appelle").
+fn split_reflexive(head: &str) -> (String, String) {
+    for elided in ["m'", "t'", "s'", "m’", "t’", "s’"] {
+        if let Some(verb) = head.strip_prefix(elided) {
+            return (elided.to_string(), verb.trim().to_string());
+        }
+    }
+
+    let words = head.split_whitespace().collect::<Vec<&str>>();
+    let num_reflexive = words
+        .iter()
+        .take(words.len().saturating_sub(1))
+        .take_while(|word| matches!(**word, "me" | "te" | "se" | "nous" | "vous"))
+        .count();
+
+    (words[..num_reflexive].join(" "), words[num_reflexive..].join(" "))
+}
+
+/// "ne" elides before a vowel: "je n'aime pas".
+fn ne(next: &str) -> String {
+    match starts_with_vowel(next) {
+        true => "n'".to_string(),
+        false => "ne ".to_string(),
+    }
+}
+
+fn french(table: &ConjugationTable, form: &ConjugatedForm, variant: Variant) -> (String, String) {
+    // compound tenses invert and negate around the auxiliary: "n'a-t-il pas parlé ?"
+    let (head, participle) = match (&form.auxiliary, &form.participle) {
+        (Some(auxiliary), Some(participle)) => (auxiliary.clone(), Some(participle.clone())),
+        _ => (form.form.clone(), None),
+    };
+    let (reflexive, verb) = split_reflexive(&head);
+    let participle = participle.map(|participle| format!(" {participle}")).unwrap_or_default();
+
+    // "j'" only elides before the verb, so "je n'aime pas" and "que je n'aie pas"
+    let pronoun = match form.pronoun.strip_suffix("j'").or_else(|| form.pronoun.strip_suffix("j’")) {
+        Some(before) => format!("{before}je"),
+        None => form.pronoun.clone(),
+    };
+
+    let verb_group = match reflexive.is_empty() {
+        true => verb.clone(),
+        false if reflexive.ends_with(['\'', '’']) => format!("{reflexive}{verb}"),
+        false => format!("{reflexive} {verb}"),
+    };
+
+    if !variant.interrogative {
+        let negated = format!("{}{verb_group} pas{participle}", ne(&verb_group));
+        return (pronoun, negated);
+    }
+
+    let subjects = form.pronoun
+        .split(',')
+        .map(|subject| subject.trim().trim_end_matches(['\'', '’']))
+        .map(|subject| if subject == "j" { "je" } else { subject })
+        .collect::<Vec<&str>>();
+
+    // "est-ce que" is the usual question with "je", inversion is literary
+    let est_ce_que = || {
+        let statement = match variant.negative {
+            true => format!("je {}{verb_group} pas{participle}", ne(&verb_group)),
+            false => match starts_with_vowel(&verb_group) {
+                true => format!("j'{verb_group}{participle}"),
+                false => format!("je {verb_group}{participle}"),
+            },
+        };
+        format!("est-ce que {statement} ?")
+    };
+
+    let inverted_verb = match (subjects.first(), verb.as_str()) {
+        (Some(&"je"), "peux") => "puis".to_string(),
+        // "parle-je" becomes "parlé-je"
+        (Some(&"je"), _) if table.tense_kind == Tense::Present && verb.ends_with('e') => {
+            format!("{}é", &verb[..verb.len() - 1])
+        }
+        _ => verb.clone(),
+    };
+
+    // "parle-t-il" keeps two vowels apart
+    let joiner = match subjects.first() {
+        Some(subject) if starts_with_vowel(subject) && (verb.ends_with('e') || verb.ends_with('a')) => "-t-",
+        _ => "-",
+    };
+    let inverted = match reflexive.is_empty() {
+        true => format!("{inverted_verb}{joiner}{}", subjects.join("/")),
+        false => format!("{reflexive} {inverted_verb}{joiner}{}", subjects.join("/")).replace("' ", "'"),
+    };
+
+    let question = match variant.negative {
+        true => format!("{}{inverted} pas{participle} ?", ne(&inverted)),
+        false => format!("{inverted}{participle} ?"),
+    };
+
+    match form.person {
+        Some(Person::FirstSingular) => (pronoun, format!("{question} / {}", est_ce_que())),
+        _ => (pronoun, question),
+    }
+}
@@ -0,0 +1,774 @@
+/// Forms for the six persons, in `Person::ALL` order. "-" marks a person the
+/// tense has no form for.
+pub type Forms = [&'static str; 6];
+
+/// The principal parts of a common irregular verb. Tenses left as None are
+/// derived from the other parts the way the language's grammar does, e.g.
+/// the french imperfect from the "nous" form of the present.
+pub struct VerbModel {
+    pub language: &'static str,
+    pub infinitive: &'static str,
+    // prefixes of verbs that conjugate the same way, e.g. "de" in "devenir"
+    pub prefixes: &'static [&'static str],
+    pub present: Forms,
+    pub preterite: Option<Forms>,
+    pub imperfect: Option<Forms>,
+    pub future_stem: Option<&'static str>,
+    pub subjunctive: Option<Forms>,
+    pub subjunctive_imperfect: Option<Forms>,
+    pub imperative: Option<Forms>,
+    pub past_participle: &'static str,
+    pub present_participle: Option<&'static str>,
+}
+
+const MODEL: VerbModel = VerbModel {
+    language: "",
+    infinitive: "",
+    prefixes: &[],
+    present: ["-"; 6],
+    preterite: None,
+    imperfect: None,
+    future_stem: None,
+    subjunctive: None,
+    subjunctive_imperfect: None,
+    imperative: None,
+    past_participle: "",
+    present_participle: None,
+};
+
+pub static VERB_MODELS: &[VerbModel] = &[
+    VerbModel {
+        language: "french",
+        infinitive: "être",
+        present: ["suis", "es", "est", "sommes", "êtes", "sont"],
+        preterite: Some(["fus", "fus", "fut", "fûmes", "fûtes", "furent"]),
+        imperfect: Some(["étais", "étais", "était", "étions", "étiez", "étaient"]),
+        future_stem: Some("ser"),
+        subjunctive: Some(["sois", "sois", "soit", "soyons", "soyez", "soient"]),
+        imperative: Some(["-", "sois", "-", "soyons", "soyez", "-"]),
+        past_participle: "été",
+        present_participle: Some("étant"),
+        ..MODEL
+    },
+    VerbModel {
+        language: "french",
+        infinitive: "avoir",
+        present: ["ai", "as", "a", "avons", "avez", "ont"],
+        preterite: Some(["eus", "eus", "eut", "eûmes", "eûtes", "eurent"]),
+        future_stem: Some("aur"),
+        subjunctive: Some(["aie", "aies", "ait", "ayons", "ayez", "aient"]),
+        imperative: Some(["-", "aie", "-", "ayons", "ayez", "-"]),
+        past_participle: "eu",
+        present_participle: Some("ayant"),
+        ..MODEL
+    },
+    VerbModel {
+        language: "french",
+        infinitive: "aller",
+        present: ["vais", "vas", "va", "allons", "allez", "vont"],
+        future_stem: Some("ir"),
+        subjunctive: Some(["aille", "ailles", "aille", "allions", "alliez", "aillent"]),
+        past_participle: "allé",
+        ..MODEL
+    },
+    VerbModel {
+        language: "french",
+        infinitive: "faire",
+        prefixes: &["dé", "re", "contre", "par", "satis", "sur"],
+        present: ["fais", "fais", "fait", "faisons", "faites", "font"],
+        preterite: Some(["fis", "fis", "fit", "fîmes", "fîtes", "firent"]),
+        future_stem: Some("fer"),
+        subjunctive: Some(["fasse", "fasses", "fasse", "fassions", "fassiez", "fassent"]),
+        past_participle: "fait",
+        ..MODEL
+    },
+    VerbModel {
+        language: "french",
+        infinitive: "venir",
+        prefixes: &["de", "re", "con", "pré", "pro", "sou", "par", "inter", "sur", "sub", "ad", "circon", "contre"],
+        present: ["viens", "viens", "vient", "venons", "venez", "viennent"],
+        preterite: Some(["vins", "vins", "vint", "vînmes", "vîntes", "vinrent"]),
+        future_stem: Some("viendr"),
+        past_participle: "venu",
+        ..MODEL
+    },
+    VerbModel {
+        language: "french",
+        infinitive: "tenir",
+        prefixes: &["ob", "re", "main", "con", "sou", "appar", "dé", "entre", "abs"],
+        present: ["tiens", "tiens", "tient", "tenons", "tenez", "tiennent"],
+        preterite: Some(["tins", "tins", "tint", "tînmes", "tîntes", "tinrent"]),
+        future_stem: Some("tiendr"),
+        past_participle: "tenu",
+        ..MODEL
+    },
+    VerbModel {
+        language: "french",
+        infinitive: "pouvoir",
+        present: ["peux", "peux", "peut", "pouvons", "pouvez", "peuvent"],
+        preterite: Some(["pus", "pus", "put", "pûmes", "pûtes", "purent"]),
+        future_stem: Some("pourr"),
+        subjunctive: Some(["puisse", "puisses", "puisse", "puissions", "puissiez", "puissent"]),
+        imperative: Some(["-"; 6]),
+        past_participle: "pu",
+        ..MODEL
+    },
+    VerbModel {
+        language: "french",
+        infinitive: "vouloir",
+        present: ["veux", "veux", "veut", "voulons", "voulez", "veulent"],
+        preterite: Some(["voulus", "voulus", "voulut", "voulûmes", "voulûtes", "voulurent"]),
+        future_stem: Some("voudr"),
+        subjunctive: Some(["veuille", "veuilles", "veuille", "voulions", "vouliez", "veuillent"]),
+        imperative: Some(["-", "veuille", "-", "veuillons", "veuillez", "-"]),
+        past_participle: "voulu",
+        ..MODEL
+    },
+    VerbModel {
+        language: "french",
+        infinitive: "savoir",
+        present: ["sais", "sais", "sait", "savons", "savez", "savent"],
+        preterite: Some(["sus", "sus", "sut", "sûmes", "sûtes", "surent"]),
+        future_stem: Some("saur"),
+        subjunctive: Some(["sache", "saches", "sache", "sachions", "sachiez", "sachent"]),
+        imperative: Some(["-", "sache", "-", "sachons", "sachez", "-"]),
+        past_participle: "su",
+        present_participle: Some("sachant"),
+        ..MODEL
+    },
+    VerbModel {
+        language: "french",
+        infinitive: "devoir",
+        present: ["dois", "dois", "doit", "devons", "devez", "doivent"],
+        preterite: Some(["dus", "dus", "dut", "dûmes", "dûtes", "durent"]),
+        future_stem: Some("devr"),
+        past_participle: "dû",
+        ..MODEL
+    },
+    VerbModel {
+        language: "french",
+        infinitive: "dire",
+        present: ["dis", "dis", "dit", "disons", "dites", "disent"],
+        preterite: Some(["dis", "dis", "dit", "dîmes", "dîtes", "dirent"]),
+        future_stem: Some("dir"),
+        past_participle: "dit",
+        ..MODEL
+    },
+    VerbModel {
+        language: "french",
+        infinitive: "prendre",
+        prefixes: &["ap", "com", "re", "sur", "entre", "mé"],
+        present: ["prends", "prends", "prend", "prenons", "prenez", "prennent"],
+        preterite: Some(["pris", "pris", "prit", "prîmes", "prîtes", "prirent"]),
+        past_participle: "pris",
+        ..MODEL
+    },
+    VerbModel {
+        language: "french",
+        infinitive: "mettre",
+        prefixes: &["ad", "per", "pro", "re", "sou", "com", "compro", "dé", "trans", "o", "entre"],
+        present: ["mets", "mets", "met", "mettons", "mettez", "mettent"],
+        preterite: Some(["mis", "mis", "mit", "mîmes", "mîtes", "mirent"]),
+        past_participle: "mis",
+        ..MODEL
+    },
+    VerbModel {
+        language: "french",
+        infinitive: "voir",
+        present: ["vois", "vois", "voit", "voyons", "voyez", "voient"],
+        preterite: Some(["vis", "vis", "vit", "vîmes", "vîtes", "virent"]),
+        future_stem: Some("verr"),
+        past_participle: "vu",
+        ..MODEL
+    },
+    VerbModel {
+        language: "french",
+        infinitive: "partir",
+        prefixes: &["re", "dé"],
+        present: ["pars", "pars", "part", "partons", "partez", "partent"],
+        past_participle: "parti",
+        ..MODEL
+    },
+    VerbModel {
+        language: "french",
+        infinitive: "sortir",
+        present: ["sors", "sors", "sort", "sortons", "sortez", "sortent"],
+        past_participle: "sorti",
+        ..MODEL
+    },
+    VerbModel {
+        language: "french",
+        infinitive: "dormir",
+        prefixes: &["en", "ren", "re"],
+        present: ["dors", "dors", "dort", "dormons", "dormez", "dorment"],
+        past_participle: "dormi",
+        ..MODEL
+    },
+    VerbModel {
+        language: "french",
+        infinitive: "ouvrir",
+        prefixes: &["r", "entr", "c", "déc", "rec"],
+        present: ["ouvre", "ouvres", "ouvre", "ouvrons", "ouvrez", "ouvrent"],
+        past_participle: "ouvert",
+        ..MODEL
+    },
+    VerbModel {
+        language: "french",
+        infinitive: "offrir",
+        present: ["offre", "offres", "offre", "offrons", "offrez", "offrent"],
+        past_participle: "offert",
+        ..MODEL
+    },
+    VerbModel {
+        language: "french",
+        infinitive: "connaître",
+        prefixes: &["re", "mé"],
+        present: ["connais", "connais", "connaît", "connaissons", "connaissez", "connaissent"],
+        preterite: Some(["connus", "connus", "connut", "connûmes", "connûtes", "connurent"]),
+        past_participle: "connu",
+        ..MODEL
+    },
+    VerbModel {
+        language: "french",
+        infinitive: "écrire",
+        prefixes: &["d", "r", "ré"],
+        present: ["écris", "écris", "écrit", "écrivons", "écrivez", "écrivent"],
+        preterite: Some(["écrivis", "écrivis", "écrivit", "écrivîmes", "écrivîtes", "écrivirent"]),
+        past_participle: "écrit",
+        ..MODEL
+    },
+    VerbModel {
+        language: "french",
+        infinitive: "lire",
+        present: ["lis", "lis", "lit", "lisons", "lisez", "lisent"],
+        preterite: Some(["lus", "lus", "lut", "lûmes", "lûtes", "lurent"]),
+        past_participle: "lu",
+        ..MODEL
+    },
+    VerbModel {
+        language: "french",
+        infinitive: "boire",
+        present: ["bois", "bois", "boit", "buvons", "buvez", "boivent"],
+        preterite: Some(["bus", "bus", "but", "bûmes", "bûtes", "burent"]),
+        past_participle: "bu",
+        ..MODEL
+    },
+    VerbModel {
+        language: "french",
+        infinitive: "croire",
+        present: ["crois", "crois", "croit", "croyons", "croyez", "croient"],
+        preterite: Some(["crus", "crus", "crut", "crûmes", "crûtes", "crurent"]),
+        past_participle: "cru",
+        ..MODEL
+    },
+    VerbModel {
+        language: "french",
+        infinitive: "recevoir",
+        present: ["reçois", "reçois", "reçoit", "recevons", "recevez", "reçoivent"],
+        preterite: Some(["reçus", "reçus", "reçut", "reçûmes", "reçûtes", "reçurent"]),
+        future_stem: Some("recevr"),
+        past_participle: "reçu",
+        ..MODEL
+    },
+    VerbModel {
+        language: "french",
+        infinitive: "vivre",
+        prefixes: &["re", "sur"],
+        present: ["vis", "vis", "vit", "vivons", "vivez", "vivent"],
+        preterite: Some(["vécus", "vécus", "vécut", "vécûmes", "vécûtes", "vécurent"]),
+        past_participle: "vécu",
+        ..MODEL
+    },
+    VerbModel {
+        language: "french",
+        infinitive: "mourir",
+        present: ["meurs", "meurs", "meurt", "mourons", "mourez", "meurent"],
+        preterite: Some(["mourus", "mourus", "mourut", "mourûmes", "mourûtes", "moururent"]),
+        future_stem: Some("mourr"),
+        past_participle: "mort",
+        ..MODEL
+    },
+    VerbModel {
+        language: "french",
+        infinitive: "naître",
+        present: ["nais", "nais", "naît", "naissons", "naissez", "naissent"],
+        preterite: Some(["naquis", "naquis", "naquit", "naquîmes", "naquîtes", "naquirent"]),
+        past_participle: "né",
+        ..MODEL
+    },
+
+    VerbModel {
+        language: "spanish",
+        infinitive: "ser",
+        present: ["soy", "eres", "es", "somos", "sois", "son"],
+        preterite: Some(["fui", "fuiste", "fue", "fuimos", "fuisteis", "fueron"]),
+        imperfect: Some(["era", "eras", "era", "éramos", "erais", "eran"]),
+        subjunctive: Some(["sea", "seas", "sea", "seamos", "seáis", "sean"]),
+        imperative: Some(["-", "sé", "sea", "seamos", "sed", "sean"]),
+        past_participle: "sido",
+        ..MODEL
+    },
+    VerbModel {
+        language: "spanish",
+        infinitive: "estar",
+        present: ["estoy", "estás", "está", "estamos", "estáis", "están"],
+        preterite: Some(["estuve", "estuviste", "estuvo", "estuvimos", "estuvisteis", "estuvieron"]),
+        subjunctive: Some(["esté", "estés", "esté", "estemos", "estéis", "estén"]),
+        past_participle: "estado",
+        ..MODEL
+    },
+    VerbModel {
+        language: "spanish",
+        infinitive: "haber",
+        present: ["he", "has", "ha", "hemos", "habéis", "han"],
+        preterite: Some(["hube", "hubiste", "hubo", "hubimos", "hubisteis", "hubieron"]),
+        future_stem: Some("habr"),
+        subjunctive: Some(["haya", "hayas", "haya", "hayamos", "hayáis", "hayan"]),
+        imperative: Some(["-", "he", "haya", "hayamos", "habed", "hayan"]),
+        past_participle: "habido",
+        ..MODEL
+    },
+    VerbModel {
+        language: "spanish",
+        infinitive: "tener",
+        prefixes: &["con", "de", "man", "ob", "re", "sos", "abs", "entre", "a"],
+        present: ["tengo", "tienes", "tiene", "tenemos", "tenéis", "tienen"],
+        preterite: Some(["tuve", "tuviste", "tuvo", "tuvimos", "tuvisteis", "tuvieron"]),
+        future_stem: Some("tendr"),
+        imperative: Some(["-", "ten", "tenga", "tengamos", "tened", "tengan"]),
+        past_participle: "tenido",
+        ..MODEL
+    },
+    VerbModel {
+        language: "spanish",
+        infinitive: "ir",
+        present: ["voy", "vas", "va", "vamos", "vais", "van"],
+        preterite: Some(["fui", "fuiste", "fue", "fuimos", "fuisteis", "fueron"]),
+        imperfect: Some(["iba", "ibas", "iba", "íbamos", "ibais", "iban"]),
+        future_stem: Some("ir"),
+        subjunctive: Some(["vaya", "vayas", "vaya", "vayamos", "vayáis", "vayan"]),
+        imperative: Some(["-", "ve", "vaya", "vamos", "id", "vayan"]),
+        past_participle: "ido",
+        present_participle: Some("yendo"),
+        ..MODEL
+    },
+    VerbModel {
+        language: "spanish",
+        infinitive: "hacer",
+        prefixes: &["des", "re", "contra"],
+        present: ["hago", "haces", "hace", "hacemos", "hacéis", "hacen"],
+        preterite: Some(["hice", "hiciste", "hizo", "hicimos", "hicisteis", "hicieron"]),
+        future_stem: Some("har"),
+        imperative: Some(["-", "haz", "haga", "hagamos", "haced", "hagan"]),
+        past_participle: "hecho",
+        ..MODEL
+    },
+    VerbModel {
+        language: "spanish",
+        infinitive: "poder",
+        present: ["puedo", "puedes", "puede", "podemos", "podéis", "pueden"],
+        preterite: Some(["pude", "pudiste", "pudo", "pudimos", "pudisteis", "pudieron"]),
+        future_stem: Some("podr"),
+        subjunctive: Some(["pueda", "puedas", "pueda", "podamos", "podáis", "puedan"]),
+        past_participle: "podido",
+        present_participle: Some("pudiendo"),
+        ..MODEL
+    },
+    VerbModel {
+        language: "spanish",
+        infinitive: "decir",
+        present: ["digo", "dices", "dice", "decimos", "decís", "dicen"],
+        preterite: Some(["dije", "dijiste", "dijo", "dijimos", "dijisteis", "dijeron"]),
+        future_stem: Some("dir"),
+        imperative: Some(["-", "di", "diga", "digamos", "decid", "digan"]),
+        past_participle: "dicho",
+        present_participle: Some("diciendo"),
+        ..MODEL
+    },
+    VerbModel {
+        language: "spanish",
+        infinitive: "venir",
+        prefixes: &["con", "de", "pre", "pro", "inter", "sobre", "re", "a"],
+        present: ["vengo", "vienes", "viene", "venimos", "venís", "vienen"],
+        preterite: Some(["vine", "viniste", "vino", "vinimos", "vinisteis", "vinieron"]),
+        future_stem: Some("vendr"),
+        imperative: Some(["-", "ven", "venga", "vengamos", "venid", "vengan"]),
+        past_participle: "venido",
+        present_participle: Some("viniendo"),
+        ..MODEL
+    },
+    VerbModel {
+        language: "spanish",
+        infinitive: "querer",
+        present: ["quiero", "quieres", "quiere", "queremos", "queréis", "quieren"],
+        preterite: Some(["quise", "quisiste", "quiso", "quisimos", "quisisteis", "quisieron"]),
+        future_stem: Some("querr"),
+        subjunctive: Some(["quiera", "quieras", "quiera", "queramos", "queráis", "quieran"]),
+        past_participle: "querido",
+        ..MODEL
+    },
+    VerbModel {
+        language: "spanish",
+        infinitive: "saber",
+        present: ["sé", "sabes", "sabe", "sabemos", "sabéis", "saben"],
+        preterite: Some(["supe", "supiste", "supo", "supimos", "supisteis", "supieron"]),
+        future_stem: Some("sabr"),
+        subjunctive: Some(["sepa", "sepas", "sepa", "sepamos", "sepáis", "sepan"]),
+        past_participle: "sabido",
+        ..MODEL
+    },
+    VerbModel {
+        language: "spanish",
+        infinitive: "poner",
+        prefixes: &["com", "su", "pro", "ex", "im", "dis", "o", "re", "de", "presu", "contra", "pos", "inter", "trans", "ante", "descom"],
+        present: ["pongo", "pones", "pone", "ponemos", "ponéis", "ponen"],
+        preterite: Some(["puse", "pusiste", "puso", "pusimos", "pusisteis", "pusieron"]),
+        future_stem: Some("pondr"),
+        imperative: Some(["-", "pon", "ponga", "pongamos", "poned", "pongan"]),
+        past_participle: "puesto",
+        ..MODEL
+    },
+    VerbModel {
+        language: "spanish",
+        infinitive: "dar",
+        present: ["doy", "das", "da", "damos", "dais", "dan"],
+        preterite: Some(["di", "diste", "dio", "dimos", "disteis", "dieron"]),
+        subjunctive: Some(["dé", "des", "dé", "demos", "deis", "den"]),
+        past_participle: "dado",
+        ..MODEL
+    },
+    VerbModel {
+        language: "spanish",
+        infinitive: "ver",
+        present: ["veo", "ves", "ve", "vemos", "veis", "ven"],
+        preterite: Some(["vi", "viste", "vio", "vimos", "visteis", "vieron"]),
+        imperfect: Some(["veía", "veías", "veía", "veíamos", "veíais", "veían"]),
+        past_participle: "visto",
+        ..MODEL
+    },
+    VerbModel {
+        language: "spanish",
+        infinitive: "salir",
+        present: ["salgo", "sales", "sale", "salimos", "salís", "salen"],
+        future_stem: Some("saldr"),
+        imperative: Some(["-", "sal", "salga", "salgamos", "salid", "salgan"]),
+        past_participle: "salido",
+        ..MODEL
+    },
+    VerbModel {
+        language: "spanish",
+        infinitive: "traer",
+        prefixes: &["a", "con", "dis", "ex", "re", "sus", "abs", "sub", "contra"],
+        present: ["traigo", "traes", "trae", "traemos", "traéis", "traen"],
+        preterite: Some(["traje", "trajiste", "trajo", "trajimos", "trajisteis", "trajeron"]),
+        past_participle: "traído",
+        present_participle: Some("trayendo"),
+        ..MODEL
+    },
+    VerbModel {
+        language: "spanish",
+        infinitive: "pensar",
+        present: ["pienso", "piensas", "piensa", "pensamos", "pensáis", "piensan"],
+        subjunctive: Some(["piense", "pienses", "piense", "pensemos", "penséis", "piensen"]),
+        past_participle: "pensado",
+        ..MODEL
+    },
+    VerbModel {
+        language: "spanish",
+        infinitive: "volver",
+        prefixes: &["de", "en", "re", "desen"],
+        present: ["vuelvo", "vuelves", "vuelve", "volvemos", "volvéis", "vuelven"],
+        subjunctive: Some(["vuelva", "vuelvas", "vuelva", "volvamos", "volváis", "vuelvan"]),
+        past_participle: "vuelto",
+        ..MODEL
+    },
+    VerbModel {
+        language: "spanish",
+        infinitive: "pedir",
+        present: ["pido", "pides", "pide", "pedimos", "pedís", "piden"],
+        preterite: Some(["pedí", "pediste", "pidió", "pedimos", "pedisteis", "pidieron"]),
+        subjunctive: Some(["pida", "pidas", "pida", "pidamos", "pidáis", "pidan"]),
+        past_participle: "pedido",
+        present_participle: Some("pidiendo"),
+        ..MODEL
+    },
+    VerbModel {
+        language: "spanish",
+        infinitive: "dormir",
+        present: ["duermo", "duermes", "duerme", "dormimos", "dormís", "duermen"],
+        preterite: Some(["dormí", "dormiste", "durmió", "dormimos", "dormisteis", "durmieron"]),
+        subjunctive: Some(["duerma", "duermas", "duerma", "durmamos", "durmáis", "duerman"]),
+        past_participle: "dormido",
+        present_participle: Some("durmiendo"),
+        ..MODEL
+    },
+
+    VerbModel {
+        language: "italian",
+        infinitive: "essere",
+        present: ["sono", "sei", "è", "siamo", "siete", "sono"],
+        preterite: Some(["fui", "fosti", "fu", "fummo", "foste", "furono"]),
+        imperfect: Some(["ero", "eri", "era", "eravamo", "eravate", "erano"]),
+        future_stem: Some("sar"),
+        subjunctive: Some(["sia", "sia", "sia", "siamo", "siate", "siano"]),
+        subjunctive_imperfect: Some(["fossi", "fossi", "fosse", "fossimo", "foste", "fossero"]),
+        imperative: Some(["-", "sii", "sia", "siamo", "siate", "siano"]),
+        past_participle: "stato",
+        ..MODEL
+    },
+    VerbModel {
+        language: "italian",
+        infinitive: "avere",
+        present: ["ho", "hai", "ha", "abbiamo", "avete", "hanno"],
+        preterite: Some(["ebbi", "avesti", "ebbe", "avemmo", "aveste", "ebbero"]),
+        future_stem: Some("avr"),
+        subjunctive: Some(["abbia", "abbia", "abbia", "abbiamo", "abbiate", "abbiano"]),
+        imperative: Some(["-", "abbi", "abbia", "abbiamo", "abbiate", "abbiano"]),
+        past_participle: "avuto",
+        ..MODEL
+    },
+    VerbModel {
+        language: "italian",
+        infinitive: "andare",
+        present: ["vado", "vai", "va", "andiamo", "andate", "vanno"],
+        future_stem: Some("andr"),
+        subjunctive: Some(["vada", "vada", "vada", "andiamo", "andiate", "vadano"]),
+        imperative: Some(["-", "va'", "vada", "andiamo", "andate", "vadano"]),
+        past_participle: "andato",
+        ..MODEL
+    },
+    VerbModel {
+        language: "italian",
+        infinitive: "fare",
+        prefixes: &["dis", "ri", "contraf", "soddis", "sopraf", "stra"],
+        present: ["faccio", "fai", "fa", "facciamo", "fate", "fanno"],
+        preterite: Some(["feci", "facesti", "fece", "facemmo", "faceste", "fecero"]),
+        imperfect: Some(["facevo", "facevi", "faceva", "facevamo", "facevate", "facevano"]),
+        future_stem: Some("far"),
+        subjunctive: Some(["faccia", "faccia", "faccia", "facciamo", "facciate", "facciano"]),
+        imperative: Some(["-", "fa'", "faccia", "facciamo", "fate", "facciano"]),
+        past_participle: "fatto",
+        present_participle: Some("facendo"),
+        ..MODEL
+    },
+    VerbModel {
+        language: "italian",
+        infinitive: "dire",
+        present: ["dico", "dici", "dice", "diciamo", "dite", "dicono"],
+        preterite: Some(["dissi", "dicesti", "disse", "dicemmo", "diceste", "dissero"]),
+        imperfect: Some(["dicevo", "dicevi", "diceva", "dicevamo", "dicevate", "dicevano"]),
+        future_stem: Some("dir"),
+        subjunctive: Some(["dica", "dica", "dica", "diciamo", "diciate", "dicano"]),
+        imperative: Some(["-", "di'", "dica", "diciamo", "dite", "dicano"]),
+        past_participle: "detto",
+        present_participle: Some("dicendo"),
+        ..MODEL
+    },
+    VerbModel {
+        language: "italian",
+        infinitive: "venire",
+        prefixes: &["di", "av", "con", "pro", "ri", "sov", "inter", "per", "pre", "s"],
+        present: ["vengo", "vieni", "viene", "veniamo", "venite", "vengono"],
+        preterite: Some(["venni", "venisti", "venne", "venimmo", "veniste", "vennero"]),
+        future_stem: Some("verr"),
+        subjunctive: Some(["venga", "venga", "venga", "veniamo", "veniate", "vengano"]),
+        imperative: Some(["-", "vieni", "venga", "veniamo", "venite", "vengano"]),
+        past_participle: "venuto",
+        ..MODEL
+    },
+    VerbModel {
+        language: "italian",
+        infinitive: "stare",
+        present: ["sto", "stai", "sta", "stiamo", "state", "stanno"],
+        preterite: Some(["stetti", "stesti", "stette", "stemmo", "steste", "stettero"]),
+        future_stem: Some("star"),
+        subjunctive: Some(["stia", "stia", "stia", "stiamo", "stiate", "stiano"]),
+        subjunctive_imperfect: Some(["stessi", "stessi", "stesse", "stessimo", "steste", "stessero"]),
+        imperative: Some(["-", "sta'", "stia", "stiamo", "state", "stiano"]),
+        past_participle: "stato",
+        ..MODEL
+    },
+    VerbModel {
+        language: "italian",
+        infinitive: "dare",
+        present: ["do", "dai", "dà", "diamo", "date", "danno"],
+        preterite: Some(["diedi", "desti", "diede", "demmo", "deste", "diedero"]),
+        future_stem: Some("dar"),
+        subjunctive: Some(["dia", "dia", "dia", "diamo", "diate", "diano"]),
+        subjunctive_imperfect: Some(["dessi", "dessi", "desse", "dessimo", "deste", "dessero"]),
+        imperative: Some(["-", "da'", "dia", "diamo", "date", "diano"]),
+        past_participle: "dato",
+        ..MODEL
+    },
+    VerbModel {
+        language: "italian",
+        infinitive: "potere",
+        present: ["posso", "puoi", "può", "possiamo", "potete", "possono"],
+        future_stem: Some("potr"),
+        subjunctive: Some(["possa", "possa", "possa", "possiamo", "possiate", "possano"]),
+        imperative: Some(["-"; 6]),
+        past_participle: "potuto",
+        ..MODEL
+    },
+    VerbModel {
+        language: "italian",
+        infinitive: "volere",
+        present: ["voglio", "vuoi", "vuole", "vogliamo", "volete", "vogliono"],
+        preterite: Some(["volli", "volesti", "volle", "volemmo", "voleste", "vollero"]),
+        future_stem: Some("vorr"),
+        subjunctive: Some(["voglia", "voglia", "voglia", "vogliamo", "vogliate", "vogliano"]),
+        imperative: Some(["-"; 6]),
+        past_participle: "voluto",
+        ..MODEL
+    },
+    VerbModel {
+        language: "italian",
+        infinitive: "dovere",
+        present: ["devo", "devi", "deve", "dobbiamo", "dovete", "devono"],
+        future_stem: Some("dovr"),
+        subjunctive: Some(["debba", "debba", "debba", "dobbiamo", "dobbiate", "debbano"]),
+        imperative: Some(["-"; 6]),
+        past_participle: "dovuto",
+        ..MODEL
+    },
+    VerbModel {
+        language: "italian",
+        infinitive: "sapere",
+        present: ["so", "sai", "sa", "sappiamo", "sapete", "sanno"],
+        preterite: Some(["seppi", "sapesti", "seppe", "sapemmo", "sapeste", "seppero"]),
+        future_stem: Some("sapr"),
+        subjunctive: Some(["sappia", "sappia", "sappia", "sappiamo", "sappiate", "sappiano"]),
+        imperative: Some(["-", "sappi", "sappia", "sappiamo", "sappiate", "sappiano"]),
+        past_participle: "saputo",
+        ..MODEL
+    },
+    VerbModel {
+        language: "italian",
+        infinitive: "tenere",
+        prefixes: &["ot", "ri", "man", "con", "sos", "trat", "appar", "de", "as", "intrat"],
+        present: ["tengo", "tieni", "tiene", "teniamo", "tenete", "tengono"],
+        preterite: Some(["tenni", "tenesti", "tenne", "tenemmo", "teneste", "tennero"]),
+        future_stem: Some("terr"),
+        subjunctive: Some(["tenga", "tenga", "tenga", "teniamo", "teniate", "tengano"]),
+        imperative: Some(["-", "tieni", "tenga", "teniamo", "tenete", "tengano"]),
+        past_participle: "tenuto",
+        ..MODEL
+    },
+    VerbModel {
+        language: "italian",
+        infinitive: "uscire",
+        present: ["esco", "esci", "esce", "usciamo", "uscite", "escono"],
+        subjunctive: Some(["esca", "esca", "esca", "usciamo", "usciate", "escano"]),
+        imperative: Some(["-", "esci", "esca", "usciamo", "uscite", "escano"]),
+        past_participle: "uscito",
+        ..MODEL
+    },
+    VerbModel {
+        language: "italian",
+        infinitive: "bere",
+        present: ["bevo", "bevi", "beve", "beviamo", "bevete", "bevono"],
+        preterite: Some(["bevvi", "bevesti", "bevve", "bevemmo", "beveste", "bevvero"]),
+        imperfect: Some(["bevevo", "bevevi", "beveva", "bevevamo", "bevevate", "bevevano"]),
+        future_stem: Some("berr"),
+        subjunctive: Some(["beva", "beva", "beva", "beviamo", "beviate", "bevano"]),
+        imperative: Some(["-", "bevi", "beva", "beviamo", "bevete", "bevano"]),
+        past_participle: "bevuto",
+        present_participle: Some("bevendo"),
+        ..MODEL
+    },
+    VerbModel {
+        language: "italian",
+        infinitive: "prendere",
+        prefixes: &["ap", "com", "ri", "sor", "intra"],
+        present: ["prendo", "prendi", "prende", "prendiamo", "prendete", "prendono"],
+        preterite: Some(["presi", "prendesti", "prese", "prendemmo", "prendeste", "presero"]),
+        past_participle: "preso",
+        ..MODEL
+    },
+    VerbModel {
+        language: "italian",
+        infinitive: "mettere",
+        prefixes: &["am", "com", "per", "pro", "ri", "s", "sotto", "tras", "di", "o", "scom"],
+        present: ["metto", "metti", "mette", "mettiamo", "mettete", "mettono"],
+        preterite: Some(["misi", "mettesti", "mise", "mettemmo", "metteste", "misero"]),
+        past_participle: "messo",
+        ..MODEL
+    },
+    VerbModel {
+        language: "italian",
+        infinitive: "vedere",
+        present: ["vedo", "vedi", "vede", "vediamo", "vedete", "vedono"],
+        preterite: Some(["vidi", "vedesti", "vide", "vedemmo", "vedeste", "videro"]),
+        future_stem: Some("vedr"),
+        past_participle: "visto",
+        ..MODEL
+    },
+];
+
+/// Verbs whose compound tenses use "être" or "essere" rather than "avoir"
+/// or "avere". Spanish always uses "haber".
+static BE_AUXILIARY_VERBS: &[(&str, &str)] = &[
+    ("french", "aller"), ("french", "venir"), ("french", "devenir"), ("french", "revenir"),
+    ("french", "arriver"), ("french", "partir"), ("french", "entrer"), ("french", "rentrer"),
+    ("french", "sortir"), ("french", "naître"), ("french", "mourir"), ("french", "rester"),
+    ("french", "tomber"), ("french", "descendre"), ("french", "monter"), ("french", "retourner"),
+    ("italian", "essere"), ("italian", "andare"), ("italian", "venire"), ("italian", "stare"),
+    ("italian", "uscire"), ("italian", "arrivare"), ("italian", "partire"), ("italian", "tornare"),
+    ("italian", "entrare"), ("italian", "restare"), ("italian", "rimanere"), ("italian", "nascere"),
+    ("italian", "morire"), ("italian", "diventare"), ("italian", "cadere"), ("italian", "succedere"),
+];
+
+/// Finds the model a verb conjugates like, along with the prefix it adds to
+/// the model, e.g. ("de", venir) for "devenir".
+pub fn find_model(language: &str, infinitive: &str) -> Option<(String, &'static VerbModel)> {
+    VERB_MODELS
+        .iter()
+        .filter(|model| model.language == language)
+        .find_map(|model| {
+            let prefix = infinitive.strip_suffix(model.infinitive)?;
+            match prefix.is_empty() || model.prefixes.contains(&prefix) {
+                true => Some((prefix.to_string(), model)),
+                false => None,
+            }
+        })
+}
+
+pub fn auxiliary_verb(language: &str, infinitive: &str) -> &'static str {
+    let takes_be = BE_AUXILIARY_VERBS
+        .iter()
+        .any(|(verb_language, verb)| *verb_language == language && *verb == infinitive);
+
+    match (language, takes_be) {
+        ("french", true) => "être",
+        ("french", false) => "avoir",
+        ("italian", true) => "essere",
+        ("italian", false) => "avere",
+        _ => "haber",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn model_of(language: &str, infinitive: &str) -> Option<(String, &'static str)> {
+        find_model(language, infinitive).map(|(prefix, model)| (prefix, model.infinitive))
+    }
+
+    #[test]
+    fn known_prefixes_derive_from_the_model() {
+        assert_eq!(model_of("french", "devenir"), Some(("de".to_string(), "venir")));
+        assert_eq!(model_of("french", "repartir"), Some(("re".to_string(), "partir")));
+        assert_eq!(model_of("spanish", "mantener"), Some(("man".to_string(), "tener")));
+        assert_eq!(model_of("italian", "rifare"), Some(("ri".to_string(), "fare")));
+        assert_eq!(model_of("italian", "fare"), Some((String::new(), "fare")));
+    }
+
+    #[test]
+    fn verbs_merely_ending_like_a_model_are_not_derived() {
+        assert_eq!(model_of("italian", "tuffare"), None);
+        assert_eq!(model_of("french", "répartir"), None);
+        assert_eq!(model_of("french", "impartir"), None);
+        assert_eq!(model_of("spanish", "prever"), None);
+    }
+}
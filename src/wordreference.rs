@@ -1,19 +1,19 @@
 pub mod wordreference_utils {
-    const BASE_URL: &str = "https://www.wordreference.com";
+    pub const BASE_URL: &str = "https://www.wordreference.com";
 
-    pub fn definition_url(from_language: String, to_language: String, word: String) -> String {
+    pub fn definition_url(base_url: &str, from_language: String, to_language: String, word: String) -> String {
         let definition_postfix = format!(
             "/{}{}/",
             map_language(from_language),
             map_language(to_language)
         );
-        let definition_url = format!("{}{}{}", BASE_URL, definition_postfix, word);
+        let definition_url = format!("{}{}{}", base_url, definition_postfix, word);
         definition_url
     }
 
-    pub fn conjugation_url(language: String, verb: String) -> String {
+    pub fn conjugation_url(base_url: &str, language: String, verb: String) -> String {
         let conjugation_postfix = format!("/conj/{}verbs.aspx?v=", map_language(language));
-        let conjugation_url = format!("{}{}{}", BASE_URL, conjugation_postfix, verb);
+        let conjugation_url = format!("{}{}{}", base_url, conjugation_postfix, verb);
         conjugation_url
     }
 
@@ -27,5 +27,13 @@ pub mod wordreference_utils {
         }
     }
 
+    /// Accepts either a language name ("spanish") or its code ("es").
+    pub fn parse_language(language: &str) -> Option<String> {
+        let language = language.to_lowercase();
+        ["french", "italian", "english", "spanish"]
+            .into_iter()
+            .find(|name| *name == language || map_language(name.to_string()) == language)
+            .map(|name| name.to_string())
+    }
 
 }